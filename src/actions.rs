@@ -0,0 +1,909 @@
+use crate::{
+    dependencies::{
+        bootstrapper::{Bootstrap, BootstrapConfig, Client as BootstrapClient},
+        comet::Client as CometClient,
+    },
+    errors::BackstopManagerErrors,
+    events::BackstopManagerEvents,
+    limits, permissions,
+    storage::{self, BootstrapOutcome, BootstrapRecord, LimitKey, RebalancePlan},
+    validation::{
+        require_backstop_valid, require_min_out_ratio, require_pair_valid, require_permission,
+        require_pool_valid,
+    },
+};
+use blend_contract_sdk::backstop::Client as BackstopClient;
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contracttype, panic_with_error,
+    token::TokenClient,
+    unwrap::UnwrapOptimized,
+    vec, Address, Env, IntoVal, Map, Symbol, Vec,
+};
+
+// The manager actions. Each action validates the permissions of `from`, but expects the
+// caller to have already required auth for `from`, so several actions can be taken with a
+// single authorization. Every manager entrypoint is an action, and can be taken as part of
+// a `multicall`.
+
+/// A manager action taken as part of a `multicall`, with the arguments of the entrypoint of
+/// the same name
+#[contracttype]
+#[derive(Clone)]
+pub enum Action {
+    /// `refund_token(token, amount)`
+    RefundToken(Address, i128),
+    /// `b_claim(backstop, pool_address, min_lp_tokens_out)`
+    BClaim(Address, Address, i128),
+    /// `b_compound(backstop, pools, min_lp_tokens_out)`
+    BCompound(Address, Vec<Address>, i128),
+    /// `b_deposit(backstop, pool_address, amount)`
+    BDeposit(Address, Address, i128),
+    /// `b_queue_withdrawal(backstop, pool_address, amount)`
+    BQueueWithdrawal(Address, Address, i128),
+    /// `b_dequeue_withdrawal(backstop, pool_address, amount)`
+    BDequeueWithdrawal(Address, Address, i128),
+    /// `b_withdraw(backstop, pool_address, amount)`
+    BWithdraw(Address, Address, i128),
+    /// `b_withdraw_unlocked(backstop, pool_address)`
+    BWithdrawUnlocked(Address, Address),
+    /// `b_migrate(old_backstop, new_backstop, pool_address, amount)`
+    BMigrate(Address, Address, Address, i128),
+    /// `b_rebalance(backstop, source_pool, destination_pool, amount)`
+    BRebalance(Address, Address, Address, i128),
+    /// `cancel_rebalance(plan_id)`
+    CancelRebalance(u32),
+    /// `c_join_pool(pool_amount_out, max_amounts_in)`
+    CJoinPool(i128, Vec<i128>),
+    /// `c_join_single(token_index, token_amount_in, min_pool_amount_out)`
+    CJoinSingle(u32, i128, i128),
+    /// `c_exit_pool(burn_amount, min_amounts_out)`
+    CExitPool(i128, Vec<i128>),
+    /// `c_exit_single(token_index, burn_amount, min_token_out)`
+    CExitSingle(u32, i128, i128),
+    /// `bb_start_bootstrap(bootstrap_token_index, bootstrap_amount, pair_min, duration, pool_address)`
+    BbStartBootstrap(u32, i128, i128, u32, Address),
+    /// `bb_claim_bootstrap(bootstrap_id, backstop)`
+    BbClaimBootstrap(u32, Address),
+    /// `bb_refund_bootstrap(bootstrap_id)`
+    BbRefundBootstrap(u32),
+}
+
+/// Take a manager action on behalf of `from`
+pub fn execute(e: &Env, from: &Address, action: Action) {
+    let e = e.clone();
+    let from = from.clone();
+    match action {
+        Action::RefundToken(token, amount) => {
+            refund_token(e, from, token, amount);
+        }
+        Action::BClaim(backstop, pool_address, min_lp_tokens_out) => {
+            b_claim(e, from, backstop, pool_address, min_lp_tokens_out);
+        }
+        Action::BCompound(backstop, pools, min_lp_tokens_out) => {
+            b_compound(e, from, backstop, pools, min_lp_tokens_out);
+        }
+        Action::BDeposit(backstop, pool_address, amount) => {
+            b_deposit(e, from, backstop, pool_address, amount);
+        }
+        Action::BQueueWithdrawal(backstop, pool_address, amount) => {
+            b_queue_withdrawal(e, from, backstop, pool_address, amount);
+        }
+        Action::BDequeueWithdrawal(backstop, pool_address, amount) => {
+            b_dequeue_withdrawal(e, from, backstop, pool_address, amount);
+        }
+        Action::BWithdraw(backstop, pool_address, amount) => {
+            b_withdraw(e, from, backstop, pool_address, amount);
+        }
+        Action::BWithdrawUnlocked(backstop, pool_address) => {
+            b_withdraw_unlocked(e, from, backstop, pool_address);
+        }
+        Action::BMigrate(old_backstop, new_backstop, pool_address, amount) => {
+            b_migrate(e, from, old_backstop, new_backstop, pool_address, amount);
+        }
+        Action::BRebalance(backstop, source_pool, destination_pool, amount) => {
+            b_rebalance(e, from, backstop, source_pool, destination_pool, amount);
+        }
+        Action::CancelRebalance(plan_id) => {
+            cancel_rebalance(e, from, plan_id);
+        }
+        Action::CJoinPool(pool_amount_out, max_amounts_in) => {
+            c_join_pool(e, from, pool_amount_out, max_amounts_in);
+        }
+        Action::CJoinSingle(token_index, token_amount_in, min_pool_amount_out) => {
+            c_join_single(e, from, token_index, token_amount_in, min_pool_amount_out);
+        }
+        Action::CExitPool(burn_amount, min_amounts_out) => {
+            c_exit_pool(e, from, burn_amount, min_amounts_out);
+        }
+        Action::CExitSingle(token_index, burn_amount, min_token_out) => {
+            c_exit_single(e, from, token_index, burn_amount, min_token_out);
+        }
+        Action::BbStartBootstrap(
+            bootstrap_token_index,
+            bootstrap_amount,
+            pair_min,
+            duration,
+            pool_address,
+        ) => {
+            bb_start_bootstrap(
+                e,
+                from,
+                bootstrap_token_index,
+                bootstrap_amount,
+                pair_min,
+                duration,
+                pool_address,
+            );
+        }
+        Action::BbClaimBootstrap(bootstrap_id, backstop) => {
+            bb_claim_bootstrap(e, from, bootstrap_id, backstop);
+        }
+        Action::BbRefundBootstrap(bootstrap_id) => {
+            bb_refund_bootstrap(e, from, bootstrap_id);
+        }
+    }
+}
+
+/// Transfer tokens from the contract back to the owner
+pub fn refund_token(e: Env, from: Address, token: Address, amount: i128) {
+    require_permission(&e, &from, permissions::REFUND_TOKEN);
+    storage::extend_instance(&e);
+    limits::spend(&e, &from, LimitKey::Refund(token.clone()), amount);
+
+    let owner = storage::get_owner(&e);
+    let token_client = TokenClient::new(&e, &token);
+    token_client.transfer(&e.current_contract_address(), &owner, &amount);
+    storage::record_action(&e, "refund_token", &from, vec![&e, amount]);
+    BackstopManagerEvents::refund_token(&e, from, token, amount);
+}
+
+/// Claim the backstop deposit emissions of a pool for the contract, and add them to the
+/// claimed totals of the pair
+///
+/// Returns the BLND claimed and the backstop tokens minted with it
+pub fn claim_emissions(
+    e: &Env,
+    backstop: &Address,
+    pool_address: &Address,
+    min_lp_tokens_out: i128,
+) -> (i128, i128) {
+    // the backstop joins the comet pool with only the BLND claimed, so the increase in the
+    // comet pool's BLND balance is the BLND claimed. BLND is the 80% weighted token of the LP.
+    let comet = CometClient::new(e, &storage::get_backstop_token(e));
+    let mut blnd = None;
+    let mut blnd_weight = 0;
+    for token in comet.get_tokens().iter() {
+        let weight = comet.get_normalized_weight(&token);
+        if weight > blnd_weight {
+            blnd_weight = weight;
+            blnd = Some(token);
+        }
+    }
+    let blnd = blnd.unwrap_optimized();
+    let balance_before = comet.get_balance(&blnd);
+    let claimed = BackstopClient::new(e, backstop).claim(
+        &e.current_contract_address(),
+        &vec![e, pool_address.clone()],
+        &min_lp_tokens_out,
+    );
+    let blnd_claimed = comet.get_balance(&blnd) - balance_before;
+    storage::add_claimed_total(e, backstop, pool_address, blnd_claimed, claimed);
+    (blnd_claimed, claimed)
+}
+
+/// Claim backstop deposit emissions from a pool for the contract
+pub fn b_claim(
+    e: Env,
+    from: Address,
+    backstop: Address,
+    pool_address: Address,
+    min_lp_tokens_out: i128,
+) -> i128 {
+    require_permission(&e, &from, permissions::B_CLAIM);
+    require_pair_valid(&e, &backstop, &pool_address);
+    storage::extend_instance(&e);
+
+    let (_, claimed) = claim_emissions(&e, &backstop, &pool_address, min_lp_tokens_out);
+    storage::record_action(&e, "b_claim", &from, vec![&e, claimed]);
+    BackstopManagerEvents::b_claim(&e, from, backstop, pool_address, claimed);
+    claimed
+}
+
+/// Compound backstop deposit emissions from a list of pools for the contract
+///
+/// The backstop deposits the backstop tokens minted by a claim back into the claimed pool, so
+/// no deposit follows the claim. The contract directly invokes each claim, so the invocation
+/// tree needs no `authorize_as_current_contract` entries.
+pub fn b_compound(
+    e: Env,
+    from: Address,
+    backstop: Address,
+    pools: Vec<Address>,
+    min_lp_tokens_out: i128,
+) -> i128 {
+    require_permission(&e, &from, permissions::B_CLAIM);
+    for pool_address in pools.iter() {
+        require_pair_valid(&e, &backstop, &pool_address);
+    }
+    storage::extend_instance(&e);
+
+    let mut claimed = 0;
+    for pool_address in pools.iter() {
+        let (_, pool_claimed) = claim_emissions(&e, &backstop, &pool_address, 0);
+        claimed += pool_claimed;
+    }
+    if claimed < min_lp_tokens_out {
+        panic_with_error!(&e, BackstopManagerErrors::MinTokensOutNotMet);
+    }
+    storage::record_action(&e, "b_compound", &from, vec![&e, claimed]);
+    BackstopManagerEvents::b_compound(&e, from, backstop, pools, claimed);
+    claimed
+}
+
+/// Deposit backstop tokens from the contract into the backstop for a pool
+pub fn b_deposit(
+    e: Env,
+    from: Address,
+    backstop: Address,
+    pool_address: Address,
+    amount: i128,
+) -> i128 {
+    require_permission(&e, &from, permissions::B_DEPOSIT);
+    require_pair_valid(&e, &backstop, &pool_address);
+    storage::extend_instance(&e);
+
+    let backstop_token = storage::get_backstop_token(&e);
+    e.authorize_as_current_contract(vec![
+        &e,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: backstop_token,
+                fn_name: Symbol::new(&e, "transfer"),
+                args: vec![
+                    &e,
+                    e.current_contract_address().into_val(&e),
+                    backstop.into_val(&e),
+                    amount.into_val(&e),
+                ],
+            },
+            sub_invocations: vec![&e],
+        }),
+    ]);
+    let shares = BackstopClient::new(&e, &backstop).deposit(
+        &e.current_contract_address(),
+        &pool_address,
+        &amount,
+    );
+    storage::record_action(&e, "b_deposit", &from, vec![&e, amount, shares]);
+    BackstopManagerEvents::b_deposit(&e, from, backstop, pool_address, amount, shares);
+    shares
+}
+
+/// Queue deposited pool shares from the contract for withdraw from a backstop of a pool
+pub fn b_queue_withdrawal(
+    e: Env,
+    from: Address,
+    backstop: Address,
+    pool_address: Address,
+    amount: i128,
+) {
+    require_permission(&e, &from, permissions::B_QUEUE);
+    require_pair_valid(&e, &backstop, &pool_address);
+    storage::extend_instance(&e);
+
+    BackstopClient::new(&e, &backstop).queue_withdrawal(
+        &e.current_contract_address(),
+        &pool_address,
+        &amount,
+    );
+    storage::record_action(&e, "b_queue_withdrawal", &from, vec![&e, amount]);
+    BackstopManagerEvents::b_queue_withdrawal(&e, from, backstop, pool_address, amount);
+}
+
+/// Dequeue a currently queued pool share withdraw for the contract from the backstop of a pool
+pub fn b_dequeue_withdrawal(
+    e: Env,
+    from: Address,
+    backstop: Address,
+    pool_address: Address,
+    amount: i128,
+) {
+    require_permission(&e, &from, permissions::B_DEQUEUE);
+    require_pair_valid(&e, &backstop, &pool_address);
+    storage::extend_instance(&e);
+    require_unreserved(&e, &backstop, &pool_address, amount, false);
+
+    BackstopClient::new(&e, &backstop).dequeue_withdrawal(
+        &e.current_contract_address(),
+        &pool_address,
+        &amount,
+    );
+    storage::record_action(&e, "b_dequeue_withdrawal", &from, vec![&e, amount]);
+    BackstopManagerEvents::b_dequeue_withdrawal(&e, from, backstop, pool_address, amount);
+}
+
+/// Withdraw shares from the contract's withdraw queue for a backstop of a pool
+pub fn b_withdraw(
+    e: Env,
+    from: Address,
+    backstop: Address,
+    pool_address: Address,
+    amount: i128,
+) -> i128 {
+    require_permission(&e, &from, permissions::B_WITHDRAW);
+    require_pair_valid(&e, &backstop, &pool_address);
+    storage::extend_instance(&e);
+    require_unreserved(&e, &backstop, &pool_address, amount, true);
+
+    let tokens_out = BackstopClient::new(&e, &backstop).withdraw(
+        &e.current_contract_address(),
+        &pool_address,
+        &amount,
+    );
+    limits::spend(&e, &from, LimitKey::Withdraw, tokens_out);
+    storage::record_action(&e, "b_withdraw", &from, vec![&e, amount, tokens_out]);
+    BackstopManagerEvents::b_withdraw(&e, from, backstop, pool_address, amount, tokens_out);
+    tokens_out
+}
+
+/// Withdraw all unlocked shares from the contract's withdraw queue for a backstop of a pool
+pub fn b_withdraw_unlocked(
+    e: Env,
+    from: Address,
+    backstop: Address,
+    pool_address: Address,
+) -> i128 {
+    require_permission(&e, &from, permissions::B_WITHDRAW);
+    require_pair_valid(&e, &backstop, &pool_address);
+    storage::extend_instance(&e);
+
+    let backstop_client = BackstopClient::new(&e, &backstop);
+    let reserved = storage::get_rebalance_reserved(&e, &backstop, &pool_address);
+    let amount = available_shares(&e, &backstop, &pool_address, &reserved, true);
+    if amount <= 0 {
+        return 0;
+    }
+
+    let tokens_out =
+        backstop_client.withdraw(&e.current_contract_address(), &pool_address, &amount);
+    limits::spend(&e, &from, LimitKey::Withdraw, tokens_out);
+    storage::record_action(
+        &e,
+        "b_withdraw_unlocked",
+        &from,
+        vec![&e, amount, tokens_out],
+    );
+    BackstopManagerEvents::b_withdraw_unlocked(
+        &e,
+        from,
+        backstop,
+        pool_address,
+        amount,
+        tokens_out,
+    );
+    tokens_out
+}
+
+/// Migrate unlocked backstop shares of a pool from one backstop to another
+pub fn b_migrate(
+    e: Env,
+    from: Address,
+    old_backstop: Address,
+    new_backstop: Address,
+    pool_address: Address,
+    amount: i128,
+) -> i128 {
+    require_permission(&e, &from, permissions::B_WITHDRAW | permissions::B_DEPOSIT);
+    require_pair_valid(&e, &old_backstop, &pool_address);
+    require_pair_valid(&e, &new_backstop, &pool_address);
+    if old_backstop == new_backstop {
+        panic_with_error!(&e, BackstopManagerErrors::InvalidContractAddress);
+    }
+    storage::extend_instance(&e);
+    require_unreserved(&e, &old_backstop, &pool_address, amount, true);
+
+    let backstop_token = storage::get_backstop_token(&e);
+    let old_backstop_client = BackstopClient::new(&e, &old_backstop);
+    let new_backstop_client = BackstopClient::new(&e, &new_backstop);
+    if old_backstop_client.backstop_token() != backstop_token
+        || new_backstop_client.backstop_token() != backstop_token
+    {
+        panic_with_error!(&e, BackstopManagerErrors::BackstopTokenMismatch);
+    }
+
+    let tokens =
+        old_backstop_client.withdraw(&e.current_contract_address(), &pool_address, &amount);
+    e.authorize_as_current_contract(vec![
+        &e,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: backstop_token,
+                fn_name: Symbol::new(&e, "transfer"),
+                args: vec![
+                    &e,
+                    e.current_contract_address().into_val(&e),
+                    new_backstop.into_val(&e),
+                    tokens.into_val(&e),
+                ],
+            },
+            sub_invocations: vec![&e],
+        }),
+    ]);
+    let shares = new_backstop_client.deposit(&e.current_contract_address(), &pool_address, &tokens);
+    storage::record_action(&e, "b_migrate", &from, vec![&e, amount, tokens, shares]);
+    BackstopManagerEvents::b_migrate(
+        &e,
+        from,
+        old_backstop,
+        new_backstop,
+        pool_address,
+        amount,
+        tokens,
+        shares,
+    );
+    shares
+}
+
+/// Record a rebalance plan and queue its shares for withdrawal from the source pool
+pub fn b_rebalance(
+    e: Env,
+    from: Address,
+    backstop: Address,
+    source_pool: Address,
+    destination_pool: Address,
+    amount: i128,
+) -> u32 {
+    require_permission(&e, &from, permissions::B_QUEUE | permissions::B_DEPOSIT);
+    require_pair_valid(&e, &backstop, &source_pool);
+    require_pair_valid(&e, &backstop, &destination_pool);
+    if source_pool == destination_pool {
+        panic_with_error!(&e, BackstopManagerErrors::InvalidContractAddress);
+    }
+    storage::extend_instance(&e);
+
+    let q4w = BackstopClient::new(&e, &backstop).queue_withdrawal(
+        &e.current_contract_address(),
+        &source_pool,
+        &amount,
+    );
+    let plan_id = storage::get_next_rebalance_id(&e);
+    storage::set_next_rebalance_id(&e, plan_id + 1);
+    let plan = RebalancePlan {
+        id: plan_id,
+        backstop,
+        source_pool,
+        destination_pool,
+        shares: amount,
+        exp: q4w.exp,
+    };
+    storage::add_rebalance(&e, &plan);
+    storage::record_action(&e, "b_rebalance", &from, vec![&e, plan_id as i128, amount]);
+    BackstopManagerEvents::b_rebalance(&e, from, plan);
+    plan_id
+}
+
+/// Cancel a rebalance plan and dequeue its shares from the source pool. If the shares are no
+/// longer queued, the plan is dropped and only the shares still queued are dequeued.
+pub fn cancel_rebalance(e: Env, from: Address, plan_id: u32) {
+    require_permission(&e, &from, permissions::B_DEQUEUE);
+    storage::extend_instance(&e);
+
+    let plan = match storage::get_rebalance(&e, plan_id) {
+        Some(plan) => plan,
+        None => panic_with_error!(&e, BackstopManagerErrors::RebalanceNotFound),
+    };
+    storage::remove_rebalance(&e, &plan);
+    let amount = plan
+        .shares
+        .min(queued_shares(&e, &plan.backstop, &plan.source_pool));
+    if amount > 0 {
+        BackstopClient::new(&e, &plan.backstop).dequeue_withdrawal(
+            &e.current_contract_address(),
+            &plan.source_pool,
+            &amount,
+        );
+    }
+    storage::record_action(
+        &e,
+        "cancel_rebalance",
+        &from,
+        vec![&e, plan_id as i128, amount],
+    );
+    BackstopManagerEvents::cancel_rebalance(&e, from, plan);
+}
+
+/// Join the BLND-USDC LP
+pub fn c_join_pool(e: Env, from: Address, pool_amount_out: i128, max_amounts_in: Vec<i128>) {
+    require_permission(&e, &from, permissions::C_JOIN);
+    storage::extend_instance(&e);
+
+    let backstop_token = storage::get_backstop_token(&e);
+    let comet = CometClient::new(&e, &backstop_token);
+    let comet_tokens = comet.get_tokens();
+    let mut auths = vec![&e];
+    for index in 0..comet_tokens.len() {
+        let amount = max_amounts_in.get(index).unwrap_optimized();
+        let token_address = comet_tokens.get(index).unwrap_optimized();
+        let approval_ledger = (e.ledger().sequence() / 100000 + 1) * 100000;
+        auths.push_back(InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: token_address,
+                fn_name: Symbol::new(&e, "approve"),
+                args: vec![
+                    &e,
+                    e.current_contract_address().into_val(&e),
+                    backstop_token.into_val(&e),
+                    amount.into_val(&e),
+                    approval_ledger.into_val(&e),
+                ],
+            },
+            sub_invocations: vec![&e],
+        }));
+    }
+    e.authorize_as_current_contract(auths);
+    comet.join_pool(
+        &pool_amount_out,
+        &max_amounts_in,
+        &e.current_contract_address(),
+    );
+    storage::record_action(&e, "c_join_pool", &from, vec![&e, pool_amount_out]);
+    BackstopManagerEvents::c_join_pool(&e, from, pool_amount_out, max_amounts_in);
+}
+
+/// Join the BLND-USDC LP with a single token
+pub fn c_join_single(
+    e: Env,
+    from: Address,
+    token_index: u32,
+    token_amount_in: i128,
+    min_pool_amount_out: i128,
+) -> i128 {
+    require_permission(&e, &from, permissions::C_JOIN);
+    storage::extend_instance(&e);
+
+    let backstop_token = storage::get_backstop_token(&e);
+    let comet = CometClient::new(&e, &backstop_token);
+    let token_address = match comet.get_tokens().get(token_index) {
+        Some(address) => address,
+        None => panic_with_error!(&e, BackstopManagerErrors::InvalidTokenIndex),
+    };
+    let approval_ledger = (e.ledger().sequence() / 100000 + 1) * 100000;
+    e.authorize_as_current_contract(vec![
+        &e,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: token_address.clone(),
+                fn_name: Symbol::new(&e, "approve"),
+                args: vec![
+                    &e,
+                    e.current_contract_address().into_val(&e),
+                    backstop_token.into_val(&e),
+                    token_amount_in.into_val(&e),
+                    approval_ledger.into_val(&e),
+                ],
+            },
+            sub_invocations: vec![&e],
+        }),
+    ]);
+    let pool_amount_out = comet.dep_tokn_amt_in_get_lp_tokns_out(
+        &token_address,
+        &token_amount_in,
+        &min_pool_amount_out,
+        &e.current_contract_address(),
+    );
+    if let Some(floor) = storage::get_slippage_floors(&e).get(token_address) {
+        require_min_out_ratio(&e, token_amount_in, pool_amount_out, floor.min_lp_out_ratio);
+    }
+    storage::record_action(
+        &e,
+        "c_join_single",
+        &from,
+        vec![&e, token_amount_in, pool_amount_out],
+    );
+    BackstopManagerEvents::c_join_single(&e, from, token_index, token_amount_in, pool_amount_out);
+    pool_amount_out
+}
+
+/// Exit the BLND-USDC LP
+pub fn c_exit_pool(e: Env, from: Address, burn_amount: i128, min_amounts_out: Vec<i128>) {
+    require_permission(&e, &from, permissions::C_EXIT);
+    storage::extend_instance(&e);
+    limits::spend(&e, &from, LimitKey::Exit, burn_amount);
+
+    let backstop_token = storage::get_backstop_token(&e);
+    let comet = CometClient::new(&e, &backstop_token);
+    comet.exit_pool(
+        &burn_amount,
+        &min_amounts_out,
+        &e.current_contract_address(),
+    );
+    storage::record_action(&e, "c_exit_pool", &from, vec![&e, burn_amount]);
+    BackstopManagerEvents::c_exit_pool(&e, from, burn_amount, min_amounts_out);
+}
+
+/// Exit the BLND-USDC LP to a single token
+pub fn c_exit_single(
+    e: Env,
+    from: Address,
+    token_index: u32,
+    burn_amount: i128,
+    min_token_out: i128,
+) -> i128 {
+    require_permission(&e, &from, permissions::C_EXIT);
+    storage::extend_instance(&e);
+    limits::spend(&e, &from, LimitKey::Exit, burn_amount);
+
+    let comet = CometClient::new(&e, &storage::get_backstop_token(&e));
+    let token_address = match comet.get_tokens().get(token_index) {
+        Some(address) => address,
+        None => panic_with_error!(&e, BackstopManagerErrors::InvalidTokenIndex),
+    };
+    let token_amount_out = comet.wdr_tokn_amt_in_get_lp_tokns_out(
+        &token_address,
+        &burn_amount,
+        &min_token_out,
+        &e.current_contract_address(),
+    );
+    if let Some(floor) = storage::get_slippage_floors(&e).get(token_address) {
+        require_min_out_ratio(&e, burn_amount, token_amount_out, floor.min_token_out_ratio);
+    }
+    storage::record_action(
+        &e,
+        "c_exit_single",
+        &from,
+        vec![&e, burn_amount, token_amount_out],
+    );
+    BackstopManagerEvents::c_exit_single(&e, from, token_index, burn_amount, token_amount_out);
+    token_amount_out
+}
+
+/// Claim the proceeds of a backstop bootstrapping
+#[allow(clippy::inconsistent_digit_grouping)]
+pub fn bb_claim_bootstrap(e: Env, from: Address, bootstrap_id: u32, backstop: Address) -> i128 {
+    require_permission(&e, &from, permissions::BB_CLAIM);
+    storage::extend_instance(&e);
+    require_backstop_valid(&e, &backstop);
+
+    let backstop_bootstrapper_client =
+        BootstrapClient::new(&e, &storage::get_backstop_bootstrapper(&e));
+    let bootstrap: Bootstrap = backstop_bootstrapper_client.get_bootstrap(&bootstrap_id);
+    let comet_client = CometClient::new(&e, &storage::get_backstop_token(&e));
+
+    let backstop_token_amount = bootstrap.data.total_backstop_tokens
+        * (comet_client.get_normalized_weight(
+            &comet_client
+                .get_tokens()
+                .get(bootstrap.config.token_index)
+                .unwrap_optimized(),
+        ) as i128)
+        / 1_000_0000;
+
+    e.authorize_as_current_contract(vec![
+        &e,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: backstop.clone(),
+                fn_name: Symbol::new(&e, "deposit"),
+                args: vec![
+                    &e,
+                    e.current_contract_address().into_val(&e),
+                    bootstrap.config.pool.into_val(&e),
+                    backstop_token_amount.into_val(&e),
+                ],
+            },
+            sub_invocations: vec![
+                &e,
+                InvokerContractAuthEntry::Contract(SubContractInvocation {
+                    context: ContractContext {
+                        contract: comet_client.address,
+                        fn_name: Symbol::new(&e, "transfer"),
+                        args: vec![
+                            &e,
+                            e.current_contract_address().into_val(&e),
+                            backstop.into_val(&e),
+                            backstop_token_amount.into_val(&e),
+                        ],
+                    },
+                    sub_invocations: vec![&e],
+                }),
+            ],
+        }),
+    ]);
+
+    let claimed = backstop_bootstrapper_client.claim(&e.current_contract_address(), &bootstrap_id);
+    storage::set_bootstrap_outcome(&e, bootstrap_id, BootstrapOutcome::Claimed);
+    storage::record_action(
+        &e,
+        "bb_claim_bootstrap",
+        &from,
+        vec![&e, bootstrap_id as i128, claimed],
+    );
+    BackstopManagerEvents::bb_claim_bootstrap(&e, from, bootstrap_id, backstop, claimed);
+    claimed
+}
+
+/// Refund a cancelled backstop bootstrapping
+pub fn bb_refund_bootstrap(e: Env, from: Address, bootstrap_id: u32) -> i128 {
+    require_permission(&e, &from, permissions::BB_REFUND);
+    storage::extend_instance(&e);
+
+    let backstop_bootstrapper_client =
+        BootstrapClient::new(&e, &storage::get_backstop_bootstrapper(&e));
+
+    let refunded =
+        backstop_bootstrapper_client.refund(&e.current_contract_address(), &bootstrap_id);
+    storage::set_bootstrap_outcome(&e, bootstrap_id, BootstrapOutcome::Refunded);
+    storage::record_action(
+        &e,
+        "bb_refund_bootstrap",
+        &from,
+        vec![&e, bootstrap_id as i128, refunded],
+    );
+    BackstopManagerEvents::bb_refund_bootstrap(&e, from, bootstrap_id, refunded);
+    refunded
+}
+
+/// Create a backstop bootstrapping with a token of the backstop token
+pub fn bb_start_bootstrap(
+    e: Env,
+    from: Address,
+    bootstrap_token_index: u32,
+    bootstrap_amount: i128,
+    pair_min: i128,
+    duration: u32,
+    pool_address: Address,
+) -> u32 {
+    require_permission(&e, &from, permissions::BB_START);
+    storage::extend_instance(&e);
+    require_pool_valid(&e, &pool_address);
+
+    let backstop_token = storage::get_backstop_token(&e);
+    let bootstrap_token: Address = match CometClient::new(&e, &backstop_token)
+        .get_tokens()
+        .get(bootstrap_token_index)
+    {
+        Some(address) => address,
+        None => panic_with_error!(e, BackstopManagerErrors::InvalidTokenIndex),
+    };
+
+    let backstop_bootstrapper = storage::get_backstop_bootstrapper(&e);
+    e.authorize_as_current_contract(vec![
+        &e,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: bootstrap_token,
+                fn_name: Symbol::new(&e, "transfer"),
+                args: vec![
+                    &e,
+                    e.current_contract_address().into_val(&e),
+                    backstop_bootstrapper.into_val(&e),
+                    bootstrap_amount.into_val(&e),
+                ],
+            },
+            sub_invocations: vec![&e],
+        }),
+    ]);
+
+    let close_ledger = e.ledger().sequence() + duration;
+    let bootstrap_id =
+        BootstrapClient::new(&e, &backstop_bootstrapper).bootstrap(&BootstrapConfig {
+            bootstrapper: e.current_contract_address(),
+            amount: bootstrap_amount,
+            close_ledger,
+            pair_min,
+            pool: pool_address.clone(),
+            token_index: bootstrap_token_index,
+        });
+    storage::set_bootstrap(
+        &e,
+        &BootstrapRecord {
+            id: bootstrap_id,
+            pool: pool_address.clone(),
+            token_index: bootstrap_token_index,
+            amount: bootstrap_amount,
+            pair_min,
+            close_ledger,
+            outcome: BootstrapOutcome::Open,
+        },
+    );
+    storage::record_action(
+        &e,
+        "bb_start_bootstrap",
+        &from,
+        vec![&e, bootstrap_id as i128, bootstrap_amount],
+    );
+    BackstopManagerEvents::bb_start_bootstrap(
+        &e,
+        from,
+        bootstrap_id,
+        bootstrap_token_index,
+        bootstrap_amount,
+        pool_address,
+    );
+    bootstrap_id
+}
+
+/// Get the shares the contract has queued for withdrawal from the backstop of a pool
+fn queued_shares(e: &Env, backstop: &Address, pool_address: &Address) -> i128 {
+    let balance =
+        BackstopClient::new(e, backstop).user_balance(pool_address, &e.current_contract_address());
+    let mut amount = 0;
+    for entry in balance.q4w.iter() {
+        amount += entry.amount;
+    }
+    amount
+}
+
+/// Get the queued shares of the backstop of a pool that can be removed while leaving the
+/// shares reserved by open rebalance plans queued. If `unlocked` is set, only shares that have
+/// unlocked can be removed, and a plan only reserves unlocked shares once its own shares unlock.
+fn available_shares(
+    e: &Env,
+    backstop: &Address,
+    pool_address: &Address,
+    reserved: &Map<u64, i128>,
+    unlocked: bool,
+) -> i128 {
+    let balance =
+        BackstopClient::new(e, backstop).user_balance(pool_address, &e.current_contract_address());
+    if !unlocked {
+        // dequeues take shares from the back of the queue, so any shares beyond the reserved
+        // total can be dequeued
+        let mut available = 0;
+        for entry in balance.q4w.iter() {
+            available += entry.amount;
+        }
+        for shares in reserved.values().iter() {
+            available -= shares;
+        }
+        return available;
+    }
+
+    // withdrawals take unlocked shares from the front of the queue, so each plan must remain
+    // covered by the queued shares that unlock no later than its own
+    let timestamp = e.ledger().timestamp();
+    let mut checkpoints = vec![e, timestamp];
+    for exp in reserved.keys().iter() {
+        if exp > timestamp {
+            checkpoints.push_back(exp);
+        }
+    }
+    let mut available = i128::MAX;
+    for checkpoint in checkpoints.iter() {
+        let mut covered = 0;
+        for entry in balance.q4w.iter() {
+            if entry.exp <= checkpoint {
+                covered += entry.amount;
+            }
+        }
+        for (exp, shares) in reserved.iter() {
+            if exp <= checkpoint {
+                covered -= shares;
+            }
+        }
+        available = available.min(covered);
+    }
+    available
+}
+
+/// Require that removing `amount` queued shares from the backstop of a pool leaves the shares
+/// reserved by open rebalance plans queued. If `unlocked` is set, only shares that have
+/// unlocked can be removed.
+///
+/// ### Errors
+/// * SharesReserved - The amount includes shares reserved by a rebalance plan
+fn require_unreserved(
+    e: &Env,
+    backstop: &Address,
+    pool_address: &Address,
+    amount: i128,
+    unlocked: bool,
+) {
+    let reserved = storage::get_rebalance_reserved(e, backstop, pool_address);
+    if !reserved.is_empty()
+        && amount > available_shares(e, backstop, pool_address, &reserved, unlocked)
+    {
+        panic_with_error!(e, BackstopManagerErrors::SharesReserved);
+    }
+}
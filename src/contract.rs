@@ -1,21 +1,99 @@
+// the client and args generated by `contractimpl` mirror the entrypoints, some of which take
+// more than 7 arguments
+#![allow(clippy::too_many_arguments)]
+
 use crate::{
-    dependencies::{
-        bootstrapper::{Bootstrap, BootstrapConfig, Client as BootstrapClient},
-        comet::Client as CometClient,
-    },
+    actions::{self, Action},
+    dependencies::{bootstrapper::Client as BootstrapClient, comet::Client as CometClient},
     errors::BackstopManagerErrors,
-    storage::{self, Manager},
+    events::BackstopManagerEvents,
+    limits, permissions,
+    storage::{
+        self, ActionRecord, BootstrapRecord, ClaimedTotal, ConfigChange, KeeperConfig, Limit,
+        LimitKey, ManagerConfig, Pair, PendingChange, PendingOwner, RebalancePlan, SlippageFloor,
+    },
+    validation::{require_min_out_ratio, require_pair_valid, SCALAR_7},
 };
 use blend_contract_sdk::backstop::Client as BackstopClient;
 use soroban_sdk::{
     auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
-    contract, contractimpl, panic_with_error,
+    contract, contractimpl, contracttype, map, panic_with_error,
     token::TokenClient,
-    unwrap::UnwrapOptimized,
-    vec, Address, Env, IntoVal, Symbol, Vec,
+    vec, Address, BytesN, Env, IntoVal, Map, Symbol, Vec,
 };
 
-const MAX_VALID_LIST_LEN: u32 = 4;
+const MAX_PAGE_LEN: u32 = 50;
+const MAX_MANAGERS: u32 = 10;
+const MAX_PENDING_CHANGES: u32 = 10;
+const MAX_TIMELOCK: u32 = 17280 * 30; // 30 days
+const SCHEMA_VERSION: u32 = 3;
+const MAX_HISTORY_RETENTION: u32 = 100;
+const MAX_KEEPER_BOUNTY: i128 = SCALAR_7 / 10; // 10%
+const MAX_MIN_OUT_RATIO: i128 = 1_000_000 * SCALAR_7;
+
+/// Shares queued for withdrawal from a backstop
+#[contracttype]
+pub struct QueuedWithdrawal {
+    /// The amount of shares queued
+    pub amount: i128,
+    /// The timestamp the shares can be withdrawn
+    pub exp: u64,
+}
+
+/// The backstop deposit held by the contract for a backstop and pool pair
+#[contracttype]
+pub struct Position {
+    /// The address of the backstop contract
+    pub backstop: Address,
+    /// The address of the pool
+    pub pool: Address,
+    /// The shares not queued for withdrawal
+    pub shares: i128,
+    /// The backstop token value of the shares not queued for withdrawal
+    pub tokens: i128,
+    /// The shares queued for withdrawal
+    pub q4w: Vec<QueuedWithdrawal>,
+    /// The backstop token value of the shares queued for withdrawal
+    pub q4w_tokens: i128,
+}
+
+/// The value held by the contract in one of the underlying tokens of the backstop token
+#[contracttype]
+pub struct AssetValue {
+    /// The address of the token
+    pub token: Address,
+    /// The amount of the token held by the contract
+    pub held: i128,
+    /// The amount of the token backing the backstop tokens held and deposited by the contract
+    pub lp_value: i128,
+    /// The total amount of the token the contract holds or has a claim on
+    pub total: i128,
+}
+
+/// The net asset value of the contract for a page of the backstop and pool pairs
+#[contracttype]
+pub struct NetAssetValue {
+    /// The backstop tokens held by the contract, or 0 if this is not the first page
+    pub lp_held: i128,
+    /// The backstop token value of the shares deposited by the contract into the pairs of
+    /// the page
+    pub lp_deposited: i128,
+    /// The value held by the contract in each underlying token of the backstop token
+    pub assets: Vec<AssetValue>,
+}
+
+/// A bootstrap started by the contract, with its live state from the backstop bootstrapper
+#[contracttype]
+pub struct BootstrapInfo {
+    /// The bootstrap as recorded when it was started
+    pub record: BootstrapRecord,
+    /// The status of the bootstrap. 0 = Active, 1 = Closing, 2 = Completed, 3 = Cancelled
+    pub status: u32,
+    /// The total amount of the pair token deposited into the bootstrap
+    pub total_pair: i128,
+    /// The total backstop tokens minted when the bootstrap closed
+    pub total_backstop_tokens: i128,
+}
 
 #[contract]
 pub struct BackstopManager;
@@ -28,15 +106,18 @@ impl BackstopManager {
     ///
     /// ### Arguments
     /// * owner - The address of the owner of the funds
-    /// * manager - The address of the manager of the funds
+    /// * manager - The address of the initial manager of the funds
+    /// * admin_scope - The scope preset of the initial manager. 0 = Low, 1 = Medium, 2 = High
     /// * bootstrapper - The address of the backstop bootstrapper contract
     /// * backstop_token - The address of the backstop token the manager can interact with. This is fixed
     ///                    as the backstop manager only supports the BLND-USDC LP token as the backstop token.
     /// * backstops - The addresses of the backstops the manager can interact with initially
     /// * pools - The addresses of the pools the manager can interact with initially
+    /// * timelock - The number of ledgers owner configuration changes are delayed. 0 = disabled
     ///
     /// ### Errors
     /// * AlreadyInitializedError - The contract has already been initialized
+    /// * InvalidTimelock - The timelock is over the maximum
     pub fn __constructor(
         e: Env,
         owner: Address,
@@ -46,29 +127,48 @@ impl BackstopManager {
         backstop_token: Address,
         backstops: Vec<Address>,
         pools: Vec<Address>,
+        timelock: u32,
     ) {
         storage::set_owner(&e, &owner);
-        if admin_scope > 2 {
-            panic_with_error!(&e, BackstopManagerErrors::InvalidScope);
-        }
-        storage::set_manager(
+        let manager_permissions = require_scope_permissions(&e, admin_scope);
+        storage::set_managers(
             &e,
-            &Manager {
-                id: manager,
-                scope: admin_scope,
-            },
+            &map![
+                &e,
+                (
+                    manager,
+                    ManagerConfig {
+                        permissions: manager_permissions,
+                        expires_at_ledger: None,
+                    }
+                )
+            ],
         );
         storage::set_backstop_bootstrapper(&e, bootstrapper);
         storage::set_backstop_token(&e, backstop_token);
 
-        if backstops.len() > MAX_VALID_LIST_LEN {
-            panic_with_error!(&e, BackstopManagerErrors::ContractListOverMax);
+        // pair every initial backstop with every initial pool
+        for backstop in backstops.iter() {
+            storage::add_valid_backstop(&e, &backstop);
+        }
+        for pool in pools.iter() {
+            storage::add_valid_pool(&e, &pool);
+            for backstop in backstops.iter() {
+                storage::add_pair(
+                    &e,
+                    &Pair {
+                        backstop,
+                        pool: pool.clone(),
+                    },
+                );
+            }
         }
-        if pools.len() > MAX_VALID_LIST_LEN {
-            panic_with_error!(&e, BackstopManagerErrors::ContractListOverMax);
+
+        if timelock > MAX_TIMELOCK {
+            panic_with_error!(&e, BackstopManagerErrors::InvalidTimelock);
         }
-        storage::set_valid_backstops(&e, &backstops);
-        storage::set_valid_pools(&e, &pools);
+        storage::set_timelock(&e, timelock);
+        storage::set_schema_version(&e, SCHEMA_VERSION);
         storage::extend_instance(&e);
     }
 
@@ -79,9 +179,24 @@ impl BackstopManager {
         storage::get_owner(&e)
     }
 
-    /// Get manager
-    pub fn manager(e: Env) -> Manager {
-        storage::get_manager(&e)
+    /// Get the pending owner, if an ownership transfer has been proposed
+    pub fn pending_owner(e: Env) -> Option<PendingOwner> {
+        storage::get_pending_owner(&e)
+    }
+
+    /// Get the guardian, if one is set
+    pub fn guardian(e: Env) -> Option<Address> {
+        storage::get_guardian(&e)
+    }
+
+    /// Check if manager actions are paused
+    pub fn paused(e: Env) -> bool {
+        storage::get_paused(&e)
+    }
+
+    /// Get the managers of the contract, mapped to their config
+    pub fn managers(e: Env) -> Map<Address, ManagerConfig> {
+        storage::get_managers(&e)
     }
 
     /// Get the backstop bootstrapper contract
@@ -89,14 +204,205 @@ impl BackstopManager {
         storage::get_backstop_bootstrapper(&e)
     }
 
-    /// Get the backstops this contract can interact with
-    pub fn backstops(e: Env) -> Vec<Address> {
-        storage::get_valid_backstops(&e)
+    /// Get a page of the backstops this contract can interact with
+    ///
+    /// ### Arguments
+    /// * `start` - The index of the first backstop to return
+    /// * `limit` - The maximum number of backstops to return, capped at 50
+    pub fn backstops(e: Env, start: u32, limit: u32) -> Vec<Address> {
+        storage::extend_instance(&e);
+        storage::get_valid_backstops(&e, start, limit.min(MAX_PAGE_LEN))
+    }
+
+    /// Get a page of the pools this contract can interact with
+    ///
+    /// ### Arguments
+    /// * `start` - The index of the first pool to return
+    /// * `limit` - The maximum number of pools to return, capped at 50
+    pub fn pools(e: Env, start: u32, limit: u32) -> Vec<Address> {
+        storage::extend_instance(&e);
+        storage::get_valid_pools(&e, start, limit.min(MAX_PAGE_LEN))
+    }
+
+    /// Get a page of the backstop and pool pairs this contract can interact with. Removing a
+    /// backstop or pool removes its pairs.
+    ///
+    /// ### Arguments
+    /// * `start` - The index of the first pair to return
+    /// * `limit` - The maximum number of pairs to return, capped at 50
+    pub fn pairs(e: Env, start: u32, limit: u32) -> Vec<Pair> {
+        storage::extend_instance(&e);
+        storage::get_pairs(&e, start, limit.min(MAX_PAGE_LEN))
+    }
+
+    /// Get the backstop deposits held by the contract for a page of the backstop and pool
+    /// pairs, in the same order as `pairs`
+    ///
+    /// ### Arguments
+    /// * `start` - The index of the first pair to return the position of
+    /// * `limit` - The maximum number of positions to return, capped at 50
+    pub fn positions(e: Env, start: u32, limit: u32) -> Vec<Position> {
+        let mut positions = Vec::new(&e);
+        for pair in storage::get_pairs(&e, start, limit.min(MAX_PAGE_LEN)).iter() {
+            positions.push_back(load_position(&e, pair));
+        }
+        positions
+    }
+
+    /// Get the net asset value of the contract in the underlying tokens of the backstop token,
+    /// for a page of the backstop and pool pairs. Backstop shares of each pair in the page,
+    /// including shares queued for withdrawal, are converted to backstop tokens, and backstop
+    /// tokens are converted to their share of the comet pool's token balances.
+    ///
+    /// Tokens held by the contract are only included in the first page, so the values of
+    /// every page sum to the net asset value of the contract.
+    ///
+    /// ### Arguments
+    /// * `start` - The index of the first pair to include
+    /// * `limit` - The maximum number of pairs to include, capped at 50
+    pub fn net_asset_value(e: Env, start: u32, limit: u32) -> NetAssetValue {
+        let comet = CometClient::new(&e, &storage::get_backstop_token(&e));
+        let include_held = start == 0;
+        let lp_held = if include_held {
+            comet.balance(&e.current_contract_address())
+        } else {
+            0
+        };
+        let mut lp_deposited = 0;
+        for pair in storage::get_pairs(&e, start, limit.min(MAX_PAGE_LEN)).iter() {
+            let position = load_position(&e, pair);
+            lp_deposited += position.tokens + position.q4w_tokens;
+        }
+
+        let lp_total = lp_held + lp_deposited;
+        let lp_supply = comet.get_total_supply();
+        let mut assets = Vec::new(&e);
+        for token in comet.get_tokens().iter() {
+            let held = if include_held {
+                TokenClient::new(&e, &token).balance(&e.current_contract_address())
+            } else {
+                0
+            };
+            let lp_value = if lp_supply == 0 {
+                0
+            } else {
+                lp_total * comet.get_balance(&token) / lp_supply
+            };
+            assets.push_back(AssetValue {
+                token,
+                held,
+                lp_value,
+                total: held + lp_value,
+            });
+        }
+        NetAssetValue {
+            lp_held,
+            lp_deposited,
+            assets,
+        }
+    }
+
+    /// Get the version of the instance storage layout
+    pub fn schema_version(e: Env) -> u32 {
+        storage::get_schema_version(&e)
+    }
+
+    /// Get the number of ledgers owner configuration changes are delayed. 0 = disabled
+    pub fn timelock(e: Env) -> u32 {
+        storage::get_timelock(&e)
     }
 
-    /// Get the pools this contract can interact with
-    pub fn pools(e: Env) -> Vec<Address> {
-        storage::get_valid_pools(&e)
+    /// Get the queued owner configuration changes by id
+    pub fn pending_changes(e: Env) -> Map<u32, PendingChange> {
+        storage::get_pending_changes(&e)
+    }
+
+    /// Get the BLND emissions claimed for a backstop and pool pair, and the backstop tokens
+    /// minted with them, for the current and previous reporting periods and in total
+    ///
+    /// ### Arguments
+    /// * `backstop` - The address of the backstop contract
+    /// * `pool` - The address of the pool
+    pub fn claimed(e: Env, backstop: Address, pool: Address) -> ClaimedTotal {
+        storage::get_claimed_total(&e, &Pair { backstop, pool })
+    }
+
+    /// Get a page of the bootstraps started by the contract, ordered from the oldest, with
+    /// their live state from the backstop bootstrapper
+    ///
+    /// ### Arguments
+    /// * `start` - The index of the first bootstrap to return
+    /// * `limit` - The maximum number of bootstraps to return, capped at 50
+    pub fn bootstraps(e: Env, start: u32, limit: u32) -> Vec<BootstrapInfo> {
+        let bootstrapper_client = BootstrapClient::new(&e, &storage::get_backstop_bootstrapper(&e));
+        let mut bootstraps = Vec::new(&e);
+        for record in storage::get_bootstraps(&e, start, limit.min(MAX_PAGE_LEN)).iter() {
+            let bootstrap = bootstrapper_client.get_bootstrap(&record.id);
+            bootstraps.push_back(BootstrapInfo {
+                record,
+                status: bootstrap.status as u32,
+                total_pair: bootstrap.data.total_pair,
+                total_backstop_tokens: bootstrap.data.total_backstop_tokens,
+            });
+        }
+        bootstraps
+    }
+
+    /// Get the slippage floor of single-sided joins and exits with a token, or None if no
+    /// floor is set
+    ///
+    /// ### Arguments
+    /// * `token` - The address of the underlying token of the backstop token
+    pub fn slippage_floor(e: Env, token: Address) -> Option<SlippageFloor> {
+        storage::get_slippage_floors(&e).get(token)
+    }
+
+    /// Get the bounds keepers compound emissions within, or None if keepers are disabled
+    pub fn keeper(e: Env) -> Option<KeeperConfig> {
+        storage::get_keeper(&e)
+    }
+
+    /// Get the backstop tokens left to pay keeper bounties
+    pub fn keeper_budget(e: Env) -> i128 {
+        storage::get_keeper_budget(&e)
+    }
+
+    /// Get a page of the open rebalance plans
+    ///
+    /// ### Arguments
+    /// * `start` - The index of the first plan to return
+    /// * `limit` - The maximum number of plans to return, capped at 50
+    pub fn rebalances(e: Env, start: u32, limit: u32) -> Vec<RebalancePlan> {
+        storage::get_rebalances(&e, start, limit.min(MAX_PAGE_LEN))
+    }
+
+    /// Get a page of the recorded manager actions, ordered from the most recent
+    ///
+    /// ### Arguments
+    /// * `start` - The number of recent actions to skip
+    /// * `limit` - The maximum number of actions to return, capped at 50
+    pub fn history(e: Env, start: u32, limit: u32) -> Vec<ActionRecord> {
+        storage::get_history(&e, start, limit.min(MAX_PAGE_LEN))
+    }
+
+    /// Get the number of most recent manager actions kept in the history
+    pub fn history_retention(e: Env) -> u32 {
+        storage::get_history_retention(&e)
+    }
+
+    /// Get the per period limits placed on managers
+    pub fn limits(e: Env) -> Map<LimitKey, Limit> {
+        storage::get_limits(&e)
+    }
+
+    /// Get the amount managers can still use for a limit during the current period
+    ///
+    /// Returns None if no limit is set
+    ///
+    /// ### Arguments
+    /// * `key` - The limit
+    pub fn remaining_limit(e: Env, key: LimitKey) -> Option<i128> {
+        limits::remaining(&e, &key)
     }
 
     /********** Owner **********/
@@ -114,27 +420,179 @@ impl BackstopManager {
 
         let token_client = TokenClient::new(&e, &token);
         token_client.transfer(&e.current_contract_address(), &to, &amount);
+        BackstopManagerEvents::transfer_token(&e, owner, token, to, amount);
     }
 
-    /// (Only Owner) Set the manager for the contract
+    /// (Only Owner) Propose a new owner for the contract. The new owner must accept ownership
+    /// via `accept_owner` on or before `expiration_ledger`. Replaces any existing proposal.
+    ///
+    /// ### Arguments
+    /// * `new_owner` - The address of the proposed owner
+    /// * `expiration_ledger` - The last ledger the proposed owner can accept ownership
+    ///
+    /// ### Errors
+    /// * InvalidExpiration - The expiration ledger is not in the future
+    pub fn propose_owner(e: Env, new_owner: Address, expiration_ledger: u32) {
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+        storage::extend_instance(&e);
+
+        if expiration_ledger <= e.ledger().sequence() {
+            panic_with_error!(&e, BackstopManagerErrors::InvalidExpiration);
+        }
+
+        storage::set_pending_owner(
+            &e,
+            &PendingOwner {
+                id: new_owner.clone(),
+                expiration_ledger,
+            },
+        );
+        BackstopManagerEvents::propose_owner(&e, owner, new_owner, expiration_ledger);
+    }
+
+    /// (Only Owner) Cancel a proposed ownership transfer
+    ///
+    /// ### Errors
+    /// * NoPendingOwner - No ownership transfer has been proposed
+    pub fn cancel_owner_transfer(e: Env) {
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+        storage::extend_instance(&e);
+
+        let pending_owner = match storage::get_pending_owner(&e) {
+            Some(pending_owner) => pending_owner,
+            None => panic_with_error!(&e, BackstopManagerErrors::NoPendingOwner),
+        };
+        storage::del_pending_owner(&e);
+        BackstopManagerEvents::cancel_owner_transfer(&e, owner, pending_owner.id);
+    }
+
+    /// (Only Owner) Add a manager to the contract with the permissions of a scope preset
+    ///
+    /// If a timelock is set, the change is queued and can be executed with `execute_pending`
+    /// once the timelock has passed.
+    ///
+    /// ### Arguments
+    /// * `manager` - The address of the manager
+    /// * `scope` - The scope preset of the manager. 0 = Low, 1 = Medium, 2 = High
+    /// * `expires_at_ledger` - The last ledger the manager can act, or None if the manager does not expire
+    ///
+    /// ### Errors
+    /// * ManagerAlreadyExists - The address is already a manager
+    /// * ContractListOverMax - The contract already has the maximum number of managers
+    /// * InvalidScope - The scope is not valid
+    /// * InvalidExpiration - The expiration ledger is not in the future
+    pub fn add_manager(e: Env, manager: Address, scope: u32, expires_at_ledger: Option<u32>) {
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+        storage::extend_instance(&e);
+
+        let manager_permissions = require_scope_permissions(&e, scope);
+        require_manager_expiration_valid(&e, expires_at_ledger);
+        queue_or_apply_change(
+            &e,
+            ConfigChange::AddManager(manager, manager_permissions, expires_at_ledger),
+        );
+    }
+
+    /// (Only Owner) Update an existing manager to the permissions of a scope preset
+    ///
+    /// If a timelock is set, the change is queued and can be executed with `execute_pending`
+    /// once the timelock has passed.
+    ///
+    /// ### Arguments
+    /// * `manager` - The address of the manager
+    /// * `scope` - The scope preset of the manager. 0 = Low, 1 = Medium, 2 = High
+    ///
+    /// ### Errors
+    /// * ManagerNotFound - The address is not a manager
+    /// * InvalidScope - The scope is not valid
+    pub fn update_manager(e: Env, manager: Address, scope: u32) {
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+        storage::extend_instance(&e);
+
+        let manager_permissions = require_scope_permissions(&e, scope);
+        queue_or_apply_change(
+            &e,
+            ConfigChange::SetManagerPermissions(manager, manager_permissions),
+        );
+    }
+
+    /// (Only Owner) Set an explicit set of permissions for an existing manager
+    ///
+    /// If a timelock is set, the change is queued and can be executed with `execute_pending`
+    /// once the timelock has passed.
     ///
     /// ### Arguments
     /// * `manager` - The address of the manager
-    /// * `scope` - The scope of the manager. 0 = None, 1 = Low, 2 = High
-    pub fn set_manager(e: Env, manager: Address, scope: u32) {
+    /// * `manager_permissions` - The bitmask of permissions granted to the manager
+    ///
+    /// ### Errors
+    /// * ManagerNotFound - The address is not a manager
+    /// * InvalidPermissions - The bitmask includes unknown permissions
+    pub fn set_manager_permissions(e: Env, manager: Address, manager_permissions: u32) {
         let owner = storage::get_owner(&e);
         owner.require_auth();
         storage::extend_instance(&e);
 
-        if scope > 2 {
-            panic_with_error!(&e, BackstopManagerErrors::InvalidScope);
+        if manager_permissions & !permissions::ALL != 0 {
+            panic_with_error!(&e, BackstopManagerErrors::InvalidPermissions);
         }
+        queue_or_apply_change(
+            &e,
+            ConfigChange::SetManagerPermissions(manager, manager_permissions),
+        );
+    }
+
+    /// (Only Owner) Renew an existing manager with a new expiration. Managers can be
+    /// renewed after they have expired.
+    ///
+    /// If a timelock is set, the change is queued and can be executed with `execute_pending`
+    /// once the timelock has passed.
+    ///
+    /// ### Arguments
+    /// * `manager` - The address of the manager
+    /// * `expires_at_ledger` - The last ledger the manager can act, or None if the manager does not expire
+    ///
+    /// ### Errors
+    /// * ManagerNotFound - The address is not a manager
+    /// * InvalidExpiration - The expiration ledger is not in the future
+    pub fn renew_manager(e: Env, manager: Address, expires_at_ledger: Option<u32>) {
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+        storage::extend_instance(&e);
 
-        storage::set_manager(&e, &Manager { id: manager, scope });
+        require_manager_expiration_valid(&e, expires_at_ledger);
+        queue_or_apply_change(&e, ConfigChange::RenewManager(manager, expires_at_ledger));
+    }
+
+    /// (Only Owner) Remove a manager from the contract. This is never timelocked.
+    ///
+    /// ### Arguments
+    /// * `manager` - The address of the manager
+    ///
+    /// ### Errors
+    /// * ManagerNotFound - The address is not a manager
+    pub fn remove_manager(e: Env, manager: Address) {
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+        storage::extend_instance(&e);
+
+        let mut managers = storage::get_managers(&e);
+        if managers.remove(manager.clone()).is_none() {
+            panic_with_error!(&e, BackstopManagerErrors::ManagerNotFound);
+        }
+        storage::set_managers(&e, &managers);
+        BackstopManagerEvents::remove_manager(&e, owner, manager);
     }
 
     /// (Only Owner) Set the backstop bootstrapper contract
     ///
+    /// If a timelock is set, the change is queued and can be executed with `execute_pending`
+    /// once the timelock has passed.
+    ///
     /// ### Arguments
     /// * `bootstrapper` - The address of the backstop bootstrapper contract
     pub fn set_backstop_bootstrapper(e: Env, bootstrapper: Address) {
@@ -142,57 +600,697 @@ impl BackstopManager {
         owner.require_auth();
         storage::extend_instance(&e);
 
-        storage::set_backstop_bootstrapper(&e, bootstrapper);
+        queue_or_apply_change(&e, ConfigChange::SetBackstopBootstrapper(bootstrapper));
+    }
+
+    /// (Only Owner) Add a backstop the contract can interact with
+    ///
+    /// If a timelock is set, the change is queued and can be executed with `execute_pending`
+    /// once the timelock has passed.
+    ///
+    /// ### Arguments
+    /// * `backstop` - The address of the backstop contract
+    ///
+    /// ### Errors
+    /// * AddressAlreadyListed - The backstop is already valid
+    pub fn add_backstop(e: Env, backstop: Address) {
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+        storage::extend_instance(&e);
+
+        queue_or_apply_change(&e, ConfigChange::AddBackstop(backstop));
+    }
+
+    /// (Only Owner) Remove a backstop the contract can interact with. Any pairs with the
+    /// backstop are removed as well. This is never timelocked.
+    ///
+    /// ### Arguments
+    /// * `backstop` - The address of the backstop contract
+    ///
+    /// ### Errors
+    /// * AddressNotListed - The backstop is not valid
+    pub fn remove_backstop(e: Env, backstop: Address) {
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+        storage::extend_instance(&e);
+
+        if !storage::remove_valid_backstop(&e, &backstop) {
+            panic_with_error!(&e, BackstopManagerErrors::AddressNotListed);
+        }
+        for pair in storage::remove_pairs_with(&e, &backstop).iter() {
+            BackstopManagerEvents::remove_pair(&e, owner.clone(), pair);
+        }
+        BackstopManagerEvents::remove_backstop(&e, owner, backstop);
+    }
+
+    /// (Only Owner) Add a pool the contract can interact with
+    ///
+    /// If a timelock is set, the change is queued and can be executed with `execute_pending`
+    /// once the timelock has passed.
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    ///
+    /// ### Errors
+    /// * AddressAlreadyListed - The pool is already valid
+    pub fn add_pool(e: Env, pool: Address) {
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+        storage::extend_instance(&e);
+
+        queue_or_apply_change(&e, ConfigChange::AddPool(pool));
+    }
+
+    /// (Only Owner) Remove a pool the contract can interact with. Any pairs with the pool
+    /// are removed as well. This is never timelocked.
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    ///
+    /// ### Errors
+    /// * AddressNotListed - The pool is not valid
+    pub fn remove_pool(e: Env, pool: Address) {
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+        storage::extend_instance(&e);
+
+        if !storage::remove_valid_pool(&e, &pool) {
+            panic_with_error!(&e, BackstopManagerErrors::AddressNotListed);
+        }
+        for pair in storage::remove_pairs_with(&e, &pool).iter() {
+            BackstopManagerEvents::remove_pair(&e, owner.clone(), pair);
+        }
+        BackstopManagerEvents::remove_pool(&e, owner, pool);
+    }
+
+    /// (Only Owner) Add a backstop and pool pair that managers can interact with
+    ///
+    /// If a timelock is set, the change is queued and can be executed with `execute_pending`
+    /// once the timelock has passed.
+    ///
+    /// ### Arguments
+    /// * `backstop` - The address of the backstop contract
+    /// * `pool` - The address of the pool
+    ///
+    /// ### Errors
+    /// * InvalidContractAddress - The backstop or pool is not valid
+    /// * PairAlreadyExists - The pair has already been added
+    pub fn add_pair(e: Env, backstop: Address, pool: Address) {
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+        storage::extend_instance(&e);
+
+        queue_or_apply_change(&e, ConfigChange::AddPair(Pair { backstop, pool }));
+    }
+
+    /// (Only Owner) Remove a backstop and pool pair. This is never timelocked.
+    ///
+    /// ### Arguments
+    /// * `backstop` - The address of the backstop contract
+    /// * `pool` - The address of the pool
+    ///
+    /// ### Errors
+    /// * PairNotFound - The pair has not been added
+    pub fn remove_pair(e: Env, backstop: Address, pool: Address) {
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+        storage::extend_instance(&e);
+
+        let pair = Pair { backstop, pool };
+        if !storage::remove_pair(&e, &pair) {
+            panic_with_error!(&e, BackstopManagerErrors::PairNotFound);
+        }
+        BackstopManagerEvents::remove_pair(&e, owner, pair);
+    }
+
+    /// (Only Owner) Set the timelock applied to owner configuration changes
+    ///
+    /// If a timelock is set, the change is queued and can be executed with `execute_pending`
+    /// once the current timelock has passed.
+    ///
+    /// ### Arguments
+    /// * `timelock` - The number of ledgers configuration changes are delayed. 0 = disabled
+    ///
+    /// ### Errors
+    /// * InvalidTimelock - The timelock is over the maximum
+    pub fn set_timelock(e: Env, timelock: u32) {
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+        storage::extend_instance(&e);
+        if timelock > MAX_TIMELOCK {
+            panic_with_error!(&e, BackstopManagerErrors::InvalidTimelock);
+        }
+        queue_or_apply_change(&e, ConfigChange::SetTimelock(timelock));
+    }
+
+    /// (Only Owner) Cancel a pending configuration change
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the pending change
+    ///
+    /// ### Errors
+    /// * PendingChangeNotFound - No pending change exists with the id
+    pub fn cancel_pending(e: Env, id: u32) {
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+        storage::extend_instance(&e);
+
+        let mut pending_changes = storage::get_pending_changes(&e);
+        if pending_changes.remove(id).is_none() {
+            panic_with_error!(&e, BackstopManagerErrors::PendingChangeNotFound);
+        }
+        storage::set_pending_changes(&e, &pending_changes);
+        BackstopManagerEvents::cancel_pending(&e, owner, id);
+    }
+
+    /// (Only Owner) Enable permissionless keepers to compound backstop emissions, or update
+    /// their bounds. Anyone can then call `keeper_compound` for a pool paired with the backstop
+    /// once per interval.
+    ///
+    /// If a timelock is set, the change is queued and can be executed with `execute_pending`
+    /// once the timelock has passed.
+    ///
+    /// ### Arguments
+    /// * `config` - The bounds keepers compound emissions within
+    ///
+    /// ### Errors
+    /// * InvalidKeeperConfig - The interval is zero, the minimum LP-out ratio is negative or
+    ///                         over 1,000,000, or the bounty is negative or over 10%
+    pub fn set_keeper(e: Env, config: KeeperConfig) {
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+        storage::extend_instance(&e);
+
+        if config.interval == 0
+            || config.min_lp_out_ratio < 0
+            || config.min_lp_out_ratio > MAX_MIN_OUT_RATIO
+            || config.bounty < 0
+            || config.bounty > MAX_KEEPER_BOUNTY
+        {
+            panic_with_error!(&e, BackstopManagerErrors::InvalidKeeperConfig);
+        }
+        queue_or_apply_change(&e, ConfigChange::SetKeeper(config));
+    }
+
+    /// (Only Owner) Fund keeper bounties by transferring backstop tokens from the owner to
+    /// the contract. Bounties are only paid from the funded budget. This is never timelocked.
+    ///
+    /// Returns the keeper budget after funding
+    ///
+    /// ### Arguments
+    /// * `amount` - The amount of backstop tokens to fund
+    ///
+    /// ### Errors
+    /// * NegativeAmountError - The amount is not positive
+    pub fn fund_keeper(e: Env, amount: i128) -> i128 {
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+        storage::extend_instance(&e);
+
+        if amount <= 0 {
+            panic_with_error!(&e, BackstopManagerErrors::NegativeAmountError);
+        }
+        TokenClient::new(&e, &storage::get_backstop_token(&e)).transfer(
+            &owner,
+            &e.current_contract_address(),
+            &amount,
+        );
+        let budget = storage::get_keeper_budget(&e) + amount;
+        storage::set_keeper_budget(&e, budget);
+        BackstopManagerEvents::fund_keeper(&e, owner, amount, budget);
+        budget
+    }
+
+    /// (Only Owner) Disable permissionless keepers. Any remaining keeper budget is released to
+    /// the backstop tokens held by the contract. This is never timelocked.
+    ///
+    /// ### Errors
+    /// * KeeperDisabled - Keepers are not enabled
+    pub fn remove_keeper(e: Env) {
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+        storage::extend_instance(&e);
+
+        if storage::get_keeper(&e).is_none() {
+            panic_with_error!(&e, BackstopManagerErrors::KeeperDisabled);
+        }
+        storage::del_keeper(&e);
+        storage::set_keeper_budget(&e, 0);
+        BackstopManagerEvents::remove_keeper(&e, owner);
+    }
+
+    /// (Only Owner) Upgrade the contract to new WASM. If the new WASM changes the storage
+    /// layout, `migrate` must be called after the upgrade. This is timelocked.
+    ///
+    /// ### Arguments
+    /// * `new_wasm_hash` - The hash of the uploaded WASM to upgrade to
+    pub fn upgrade(e: Env, new_wasm_hash: BytesN<32>) {
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+        storage::extend_instance(&e);
+
+        queue_or_apply_change(&e, ConfigChange::Upgrade(new_wasm_hash));
+    }
+
+    /// (Only Owner) Migrate instance storage to the layout used by the current WASM
+    ///
+    /// ### Errors
+    /// * InvalidSchemaVersion - The storage is already at the current version
+    pub fn migrate(e: Env) {
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+        storage::extend_instance(&e);
+
+        let schema_version = storage::get_schema_version(&e);
+        if schema_version >= SCHEMA_VERSION {
+            panic_with_error!(&e, BackstopManagerErrors::InvalidSchemaVersion);
+        }
+        if schema_version < 1 {
+            // version 1 stores managers in a map instead of the legacy single manager record
+            storage::set_managers(&e, &storage::get_managers(&e));
+        }
+        if schema_version < 3 {
+            // version 2 added explicit backstop and pool pairs, and version 3 moved the valid
+            // backstops, pools and pairs into persistent storage
+            storage::move_legacy_allowlists(&e);
+        }
+        storage::set_schema_version(&e, SCHEMA_VERSION);
+        BackstopManagerEvents::migrate(&e, owner, schema_version, SCHEMA_VERSION);
     }
 
-    /// (Only Owner) Set the list of valid backstops
+    /// (Only Owner) Set the guardian for the contract. The guardian can pause all manager actions.
+    /// Setting a guardian when none is set applies immediately. Replacing or removing the
+    /// guardian is timelocked.
     ///
     /// ### Arguments
-    /// * `backstops` - The addresses of the backstops the manager can interact with
-    pub fn set_backstops(e: Env, backstops: Vec<Address>) {
+    /// * `guardian` - The address of the guardian, or None to remove the guardian
+    pub fn set_guardian(e: Env, guardian: Option<Address>) {
         let owner = storage::get_owner(&e);
         owner.require_auth();
         storage::extend_instance(&e);
-        if backstops.len() > MAX_VALID_LIST_LEN {
-            panic_with_error!(&e, BackstopManagerErrors::ContractListOverMax);
+
+        let change = ConfigChange::SetGuardian(guardian.clone());
+        if guardian.is_some() && storage::get_guardian(&e).is_none() {
+            apply_config_change(&e, change);
+        } else {
+            queue_or_apply_change(&e, change);
         }
-        storage::set_valid_backstops(&e, &backstops);
     }
 
-    /// (Only Owner) Set the list of valid pools
+    /// (Only Owner) Unpause manager actions
+    pub fn unpause(e: Env) {
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+        storage::extend_instance(&e);
+
+        storage::set_paused(&e, false);
+        BackstopManagerEvents::unpause(&e, owner);
+    }
+
+    /// (Only Owner) Set a per period limit on an action taken by managers. Replaces any
+    /// existing limit, and the amount used during the current period is kept. Adding a limit,
+    /// or lowering the max amount without shortening the period, applies immediately. Any other
+    /// change widens what managers can do, and is timelocked.
     ///
     /// ### Arguments
-    /// * `pools` - The addresses of the backstops the manager can interact with
-    pub fn set_pools(e: Env, pools: Vec<Address>) {
+    /// * `key` - The limit
+    /// * `max_amount` - The maximum amount managers can use each period
+    /// * `period` - The length of a period in ledgers
+    ///
+    /// ### Errors
+    /// * InvalidLimit - The max amount is negative or the period is zero
+    pub fn set_limit(e: Env, key: LimitKey, max_amount: i128, period: u32) {
         let owner = storage::get_owner(&e);
         owner.require_auth();
         storage::extend_instance(&e);
-        if pools.len() > MAX_VALID_LIST_LEN {
-            panic_with_error!(&e, BackstopManagerErrors::ContractListOverMax);
+
+        if max_amount < 0 || period == 0 {
+            panic_with_error!(&e, BackstopManagerErrors::InvalidLimit);
+        }
+        let tightens = match storage::get_limits(&e).get(key.clone()) {
+            Some(limit) => max_amount <= limit.max_amount && period >= limit.period,
+            None => true,
+        };
+        let change = ConfigChange::SetLimit(key, max_amount, period);
+        if tightens {
+            apply_config_change(&e, change);
+        } else {
+            queue_or_apply_change(&e, change);
         }
-        storage::set_valid_pools(&e, &pools);
+    }
+
+    /// (Only Owner) Set the minimum amounts managers must receive for single-sided joins and
+    /// exits of the comet pool with a token, on top of the minimum passed by the manager.
+    /// Raising either ratio applies immediately. Lowering either ratio widens what managers
+    /// can do, and is timelocked.
+    ///
+    /// ### Arguments
+    /// * `token` - The address of the underlying token of the backstop token
+    /// * `floor` - The minimum amounts out, as ratios with 7 decimals. 0 = no floor
+    ///
+    /// ### Errors
+    /// * InvalidTokenIndex - The token is not an underlying token of the backstop token
+    /// * InvalidSlippageFloor - Either ratio is negative or over 1,000,000
+    pub fn set_slippage_floor(e: Env, token: Address, floor: SlippageFloor) {
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+        storage::extend_instance(&e);
+
+        if !CometClient::new(&e, &storage::get_backstop_token(&e))
+            .get_tokens()
+            .contains(&token)
+        {
+            panic_with_error!(&e, BackstopManagerErrors::InvalidTokenIndex);
+        }
+        if floor.min_lp_out_ratio < 0
+            || floor.min_lp_out_ratio > MAX_MIN_OUT_RATIO
+            || floor.min_token_out_ratio < 0
+            || floor.min_token_out_ratio > MAX_MIN_OUT_RATIO
+        {
+            panic_with_error!(&e, BackstopManagerErrors::InvalidSlippageFloor);
+        }
+        let tightens = match storage::get_slippage_floors(&e).get(token.clone()) {
+            Some(old_floor) => {
+                floor.min_lp_out_ratio >= old_floor.min_lp_out_ratio
+                    && floor.min_token_out_ratio >= old_floor.min_token_out_ratio
+            }
+            None => true,
+        };
+        let change = ConfigChange::SetSlippageFloor(token, floor);
+        if tightens {
+            apply_config_change(&e, change);
+        } else {
+            queue_or_apply_change(&e, change);
+        }
+    }
+
+    /// (Only Owner) Remove a per period limit on an action taken by managers. This is timelocked.
+    ///
+    /// ### Arguments
+    /// * `key` - The limit
+    ///
+    /// ### Errors
+    /// * InvalidLimit - No limit is set
+    pub fn remove_limit(e: Env, key: LimitKey) {
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+        storage::extend_instance(&e);
+
+        if !storage::get_limits(&e).contains_key(key.clone()) {
+            panic_with_error!(&e, BackstopManagerErrors::InvalidLimit);
+        }
+        queue_or_apply_change(&e, ConfigChange::RemoveLimit(key));
+    }
+
+    /// (Only Owner) Set the number of most recent manager actions kept in the history.
+    /// Recorded actions outside of the new retention are removed.
+    ///
+    /// ### Arguments
+    /// * `retention` - The number of actions to keep, capped at 100. 0 = disabled
+    ///
+    /// ### Errors
+    /// * InvalidHistoryRetention - The retention is over the maximum
+    pub fn set_history_retention(e: Env, retention: u32) {
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+        storage::extend_instance(&e);
+
+        if retention > MAX_HISTORY_RETENTION {
+            panic_with_error!(&e, BackstopManagerErrors::InvalidHistoryRetention);
+        }
+        let old_retention = storage::get_history_retention(&e);
+        storage::set_history_retention(&e, retention);
+        BackstopManagerEvents::set_history_retention(&e, owner, old_retention, retention);
+    }
+
+    /// (Only Owner) Snapshot the claimed totals of a backstop and pool pair at a reporting
+    /// period boundary. The totals for the current period become the totals for the previous
+    /// period, and a new period is started. The cumulative totals are not reset.
+    ///
+    /// Returns the claimed totals after the snapshot
+    ///
+    /// ### Arguments
+    /// * `backstop` - The address of the backstop contract
+    /// * `pool` - The address of the pool
+    pub fn snapshot_claimed(e: Env, backstop: Address, pool: Address) -> ClaimedTotal {
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+        storage::extend_instance(&e);
+
+        let pair = Pair {
+            backstop: backstop.clone(),
+            pool: pool.clone(),
+        };
+        let mut total = storage::get_claimed_total(&e, &pair);
+        total.last_amount = total.amount;
+        total.last_blnd = total.blnd;
+        total.amount = 0;
+        total.blnd = 0;
+        total.period_start = e.ledger().sequence();
+        storage::set_claimed_total(&e, &pair, &total);
+        BackstopManagerEvents::snapshot_claimed(
+            &e,
+            owner,
+            backstop,
+            pool,
+            total.last_amount,
+            total.last_blnd,
+        );
+        total
+    }
+
+    /********** Guardian **********/
+
+    /// (Only Guardian) Pause all manager actions. The owner can still act while paused.
+    ///
+    /// ### Errors
+    /// * UnauthorizedError - No guardian is set
+    pub fn pause(e: Env) {
+        let guardian = match storage::get_guardian(&e) {
+            Some(guardian) => guardian,
+            None => panic_with_error!(&e, BackstopManagerErrors::UnauthorizedError),
+        };
+        guardian.require_auth();
+        storage::extend_instance(&e);
+
+        storage::set_paused(&e, true);
+        BackstopManagerEvents::pause(&e, guardian);
+    }
+
+    /********** Pending Owner **********/
+
+    /// (Only Pending Owner) Accept a proposed ownership transfer
+    ///
+    /// ### Errors
+    /// * NoPendingOwner - No ownership transfer has been proposed
+    /// * PendingOwnerExpired - The proposal expired before it was accepted
+    pub fn accept_owner(e: Env) {
+        let pending_owner = match storage::get_pending_owner(&e) {
+            Some(pending_owner) => pending_owner,
+            None => panic_with_error!(&e, BackstopManagerErrors::NoPendingOwner),
+        };
+        pending_owner.id.require_auth();
+        storage::extend_instance(&e);
+
+        if pending_owner.expiration_ledger < e.ledger().sequence() {
+            panic_with_error!(&e, BackstopManagerErrors::PendingOwnerExpired);
+        }
+
+        let old_owner = storage::get_owner(&e);
+        storage::set_owner(&e, &pending_owner.id);
+        storage::del_pending_owner(&e);
+        BackstopManagerEvents::accept_owner(&e, pending_owner.id, old_owner);
+    }
+
+    /********** Permissionless **********/
+
+    /// Execute a queued owner configuration change once its timelock has passed
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the pending change
+    ///
+    /// ### Errors
+    /// * PendingChangeNotFound - No pending change exists with the id
+    /// * PendingChangeLocked - The timelock for the change has not passed
+    pub fn execute_pending(e: Env, id: u32) {
+        storage::extend_instance(&e);
+
+        let mut pending_changes = storage::get_pending_changes(&e);
+        let pending_change = match pending_changes.get(id) {
+            Some(pending_change) => pending_change,
+            None => panic_with_error!(&e, BackstopManagerErrors::PendingChangeNotFound),
+        };
+        if e.ledger().sequence() < pending_change.execute_after {
+            panic_with_error!(&e, BackstopManagerErrors::PendingChangeLocked);
+        }
+        pending_changes.remove(id);
+        storage::set_pending_changes(&e, &pending_changes);
+        apply_config_change(&e, pending_change.change);
+    }
+
+    /// Compound backstop emissions for a pool paired with the keeper backstop, within the bounds
+    /// set by the owner. Can be called by anyone once per interval for each pool, and pays the
+    /// caller a bounty in backstop tokens from the keeper budget if one is set. The bounty is
+    /// capped at the remaining budget, and the compound succeeds even if nothing is left.
+    ///
+    /// Returns the amount of backstop tokens minted with emissions
+    ///
+    /// ### Arguments
+    /// * `from` - The caller of the function, who receives the bounty
+    /// * `pool_address` - The address of the pool
+    ///
+    /// ### Errors
+    /// * ContractPaused - Manager actions are paused
+    /// * KeeperDisabled - Keepers are not enabled
+    /// * InvalidContractAddress - The pool is not paired with the keeper backstop
+    /// * KeeperIntervalNotPassed - The pool was compounded less than an interval ago
+    /// * MinTokensOutNotMet - Less backstop tokens were minted per BLND than the minimum ratio
+    pub fn keeper_compound(e: Env, from: Address, pool_address: Address) -> i128 {
+        from.require_auth();
+        if storage::get_paused(&e) {
+            panic_with_error!(&e, BackstopManagerErrors::ContractPaused);
+        }
+        let config = match storage::get_keeper(&e) {
+            Some(config) => config,
+            None => panic_with_error!(&e, BackstopManagerErrors::KeeperDisabled),
+        };
+        require_pair_valid(&e, &config.backstop, &pool_address);
+        storage::extend_instance(&e);
+
+        let sequence = e.ledger().sequence();
+        if let Some(last_compound) = storage::get_keeper_last_compound(&e, &pool_address) {
+            if sequence < last_compound.saturating_add(config.interval) {
+                panic_with_error!(&e, BackstopManagerErrors::KeeperIntervalNotPassed);
+            }
+        }
+        storage::set_keeper_last_compound(&e, &pool_address, sequence);
+
+        let (blnd_claimed, claimed) =
+            actions::claim_emissions(&e, &config.backstop, &pool_address, 0);
+        require_min_out_ratio(&e, blnd_claimed, claimed, config.min_lp_out_ratio);
+
+        // the emissions are deposited into the backstop by the claim, so the bounty is paid
+        // from the budget funded by the owner rather than the backstop tokens minted
+        let backstop_token = TokenClient::new(&e, &storage::get_backstop_token(&e));
+        let budget = storage::get_keeper_budget(&e);
+        let bounty = (claimed * config.bounty / SCALAR_7)
+            .min(budget)
+            .min(backstop_token.balance(&e.current_contract_address()));
+        if bounty > 0 {
+            storage::set_keeper_budget(&e, budget - bounty);
+            backstop_token.transfer(&e.current_contract_address(), &from, &bounty);
+        }
+        storage::record_action(&e, "keeper_compound", &from, vec![&e, claimed, bounty]);
+        BackstopManagerEvents::keeper_compound(
+            &e,
+            from,
+            config.backstop,
+            pool_address,
+            claimed,
+            bounty,
+        );
+        claimed
+    }
+
+    /// Execute a rebalance plan once its queued shares have unlocked. The shares are withdrawn
+    /// from the source pool, and the backstop tokens returned are deposited into the
+    /// destination pool.
+    ///
+    /// Returns the number of backstop pool shares minted in the destination pool
+    ///
+    /// ### Arguments
+    /// * `from` - The caller of the function
+    /// * `plan_id` - The id of the rebalance plan
+    ///
+    /// ### Errors
+    /// * ContractPaused - Manager actions are paused
+    /// * RebalanceNotFound - No open rebalance plan exists with the id
+    /// * RebalanceLocked - The queued shares have not unlocked
+    /// * InvalidContractAddress - The source or destination pool is no longer paired with the backstop
+    pub fn execute_rebalance(e: Env, from: Address, plan_id: u32) -> i128 {
+        from.require_auth();
+        if storage::get_paused(&e) {
+            panic_with_error!(&e, BackstopManagerErrors::ContractPaused);
+        }
+        let plan = match storage::get_rebalance(&e, plan_id) {
+            Some(plan) => plan,
+            None => panic_with_error!(&e, BackstopManagerErrors::RebalanceNotFound),
+        };
+        if e.ledger().timestamp() < plan.exp {
+            panic_with_error!(&e, BackstopManagerErrors::RebalanceLocked);
+        }
+        require_pair_valid(&e, &plan.backstop, &plan.source_pool);
+        require_pair_valid(&e, &plan.backstop, &plan.destination_pool);
+        storage::extend_instance(&e);
+        storage::remove_rebalance(&e, &plan);
+
+        let backstop_client = BackstopClient::new(&e, &plan.backstop);
+        let tokens = backstop_client.withdraw(
+            &e.current_contract_address(),
+            &plan.source_pool,
+            &plan.shares,
+        );
+        e.authorize_as_current_contract(vec![
+            &e,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: storage::get_backstop_token(&e),
+                    fn_name: Symbol::new(&e, "transfer"),
+                    args: vec![
+                        &e,
+                        e.current_contract_address().into_val(&e),
+                        plan.backstop.into_val(&e),
+                        tokens.into_val(&e),
+                    ],
+                },
+                sub_invocations: vec![&e],
+            }),
+        ]);
+        let shares = backstop_client.deposit(
+            &e.current_contract_address(),
+            &plan.destination_pool,
+            &tokens,
+        );
+        BackstopManagerEvents::execute_rebalance(&e, from, plan_id, tokens, shares);
+        shares
     }
 
     /********** Manager **********/
 
-    /// (Manager, Low) Transfer tokens from the contract back to the owner
+    /// (Manager, REFUND_TOKEN) Transfer tokens from the contract back to the owner
     ///
     /// ### Arguments
     /// * `token` - The address of the token to transfer
     /// * `to` - The address to transfer the tokens to
     /// * `amount` - The amount of tokens to transfer
+    ///
+    /// ### Errors
+    /// * RefundLimitExceeded - A manager would exceed the refund limit for the token
     pub fn refund_token(e: Env, from: Address, token: Address, amount: i128) {
-        require_auth_with_scope(&e, from, 0);
-        storage::extend_instance(&e);
+        from.require_auth();
+        actions::refund_token(e, from, token, amount)
+    }
 
-        let owner = storage::get_owner(&e);
-        let token_client = TokenClient::new(&e, &token);
-        token_client.transfer(&e.current_contract_address(), &owner, &amount);
+    /// (Manager) Take a list of manager actions atomically, with a single authorization from
+    /// the caller. Each action requires the same permissions as its entrypoint, and publishes
+    /// the same events and history records.
+    ///
+    /// ### Arguments
+    /// * `from` - The caller of the function
+    /// * `actions` - The actions to take, in order
+    ///
+    /// ### Errors
+    /// If the caller does not have the permissions for any action, or any action fails
+    pub fn multicall(e: Env, from: Address, actions: Vec<Action>) {
+        from.require_auth();
+        for action in actions.iter() {
+            actions::execute(&e, &from, action);
+        }
     }
 
     /***** Backstop Interactions *****/
 
-    /// (Manager, Low) Claim backstop deposit emissions from a list of pools for the contract
+    /// (Manager, B_CLAIM) Claim backstop deposit emissions from a list of pools for the contract
     ///
     /// Returns the amount of BLND emissions claimed
     ///
@@ -211,18 +1309,40 @@ impl BackstopManager {
         pool_address: Address,
         min_lp_tokens_out: i128,
     ) -> i128 {
-        require_auth_with_scope(&e, from, 0);
-        require_backstop_and_pool_valid(&e, &backstop, &pool_address);
-        storage::extend_instance(&e);
+        from.require_auth();
+        actions::b_claim(e, from, backstop, pool_address, min_lp_tokens_out)
+    }
 
-        BackstopClient::new(&e, &backstop).claim(
-            &e.current_contract_address(),
-            &vec![&e, pool_address],
-            &min_lp_tokens_out,
-        )
+    /// (Manager, B_CLAIM) Compound backstop deposit emissions from a list of pools for the
+    /// contract. Each pool is claimed separately, and the backstop deposits the backstop tokens
+    /// minted from a pool's emissions back into the backstop for that pool. Only `from` has to
+    /// authorize the call, as the contract is the direct invoker of each claim and never holds
+    /// the minted backstop tokens.
+    ///
+    /// Returns the amount of backstop tokens minted with emissions
+    ///
+    /// ### Arguments
+    /// * `from` - The caller of the function
+    /// * `backstop` - The address of the backstop contract
+    /// * `pools` - The addresses of the pools to compound
+    /// * `min_lp_tokens_out` - The minimum amount of backstop tokens to mint with emissions
+    ///                         across all pools
+    ///
+    /// ### Errors
+    /// * InvalidContractAddress - A pool is not paired with the backstop
+    /// * MinTokensOutNotMet - Less than `min_lp_tokens_out` backstop tokens were minted
+    pub fn b_compound(
+        e: Env,
+        from: Address,
+        backstop: Address,
+        pools: Vec<Address>,
+        min_lp_tokens_out: i128,
+    ) -> i128 {
+        from.require_auth();
+        actions::b_compound(e, from, backstop, pools, min_lp_tokens_out)
     }
 
-    /// (Manger, Medium) Deposit "amount" backstop tokens from the contract into the backstop for "pool_address"
+    /// (Manager, B_DEPOSIT) Deposit "amount" backstop tokens from the contract into the backstop for "pool_address"
     ///
     /// Returns the number of backstop pool shares minted
     ///
@@ -238,35 +1358,11 @@ impl BackstopManager {
         pool_address: Address,
         amount: i128,
     ) -> i128 {
-        require_auth_with_scope(&e, from, 1);
-        require_backstop_and_pool_valid(&e, &backstop, &pool_address);
-        storage::extend_instance(&e);
-
-        let backstop_token = storage::get_backstop_token(&e);
-        e.authorize_as_current_contract(vec![
-            &e,
-            InvokerContractAuthEntry::Contract(SubContractInvocation {
-                context: ContractContext {
-                    contract: backstop_token,
-                    fn_name: Symbol::new(&e, "transfer"),
-                    args: vec![
-                        &e,
-                        e.current_contract_address().into_val(&e),
-                        backstop.into_val(&e),
-                        amount.into_val(&e),
-                    ],
-                },
-                sub_invocations: vec![&e],
-            }),
-        ]);
-        BackstopClient::new(&e, &backstop).deposit(
-            &e.current_contract_address(),
-            &pool_address,
-            &amount,
-        )
+        from.require_auth();
+        actions::b_deposit(e, from, backstop, pool_address, amount)
     }
 
-    /// (Manager, Medium) Queue deposited pool shares from the contract for withdraw from a backstop of a pool
+    /// (Manager, B_QUEUE) Queue deposited pool shares from the contract for withdraw from a backstop of a pool
     ///
     /// Returns the created queue for withdrawal
     ///
@@ -282,24 +1378,20 @@ impl BackstopManager {
         pool_address: Address,
         amount: i128,
     ) {
-        require_auth_with_scope(&e, from, 1);
-        require_backstop_and_pool_valid(&e, &backstop, &pool_address);
-        storage::extend_instance(&e);
-
-        BackstopClient::new(&e, &backstop).queue_withdrawal(
-            &e.current_contract_address(),
-            &pool_address,
-            &amount,
-        );
+        from.require_auth();
+        actions::b_queue_withdrawal(e, from, backstop, pool_address, amount)
     }
 
-    /// (Manager, Medium) Dequeue a currently queued pool share withdraw for the contract from the backstop of a pool
+    /// (Manager, B_DEQUEUE) Dequeue a currently queued pool share withdraw for the contract from the backstop of a pool
     ///
     /// ### Arguments
     /// * `from` - The caller of the function
     /// * `backstop` - The address of the backstop contract
     /// * `pool_address` - The address of the pool
     /// * `amount` - The amount of shares to dequeue
+    ///
+    /// ### Errors
+    /// * SharesReserved - The amount includes queued shares reserved by a rebalance plan
     pub fn b_dequeue_withdrawal(
         e: Env,
         from: Address,
@@ -307,18 +1399,11 @@ impl BackstopManager {
         pool_address: Address,
         amount: i128,
     ) {
-        require_auth_with_scope(&e, from, 1);
-        require_backstop_and_pool_valid(&e, &backstop, &pool_address);
-        storage::extend_instance(&e);
-
-        BackstopClient::new(&e, &backstop).dequeue_withdrawal(
-            &e.current_contract_address(),
-            &pool_address,
-            &amount,
-        )
+        from.require_auth();
+        actions::b_dequeue_withdrawal(e, from, backstop, pool_address, amount)
     }
 
-    /// (Manager, High) Withdraw shares from the contract's withdraw queue for a backstop of a pool
+    /// (Manager, B_WITHDRAW) Withdraw shares from the contract's withdraw queue for a backstop of a pool
     ///
     /// Returns the amount of tokens returned
     ///
@@ -327,6 +1412,10 @@ impl BackstopManager {
     /// * `backstop` - The address of the backstop contract
     /// * `pool_address` - The address of the pool
     /// * `amount` - The amount of shares to withdraw
+    ///
+    /// ### Errors
+    /// * WithdrawLimitExceeded - A manager would exceed the withdraw limit
+    /// * SharesReserved - The amount includes queued shares reserved by a rebalance plan
     pub fn b_withdraw(
         e: Env,
         from: Address,
@@ -334,20 +1423,111 @@ impl BackstopManager {
         pool_address: Address,
         amount: i128,
     ) -> i128 {
-        require_auth_with_scope(&e, from, 2);
-        require_backstop_and_pool_valid(&e, &backstop, &pool_address);
-        storage::extend_instance(&e);
+        from.require_auth();
+        actions::b_withdraw(e, from, backstop, pool_address, amount)
+    }
 
-        BackstopClient::new(&e, &backstop).withdraw(
-            &e.current_contract_address(),
-            &pool_address,
-            &amount,
-        )
+    /// (Manager, B_WITHDRAW) Withdraw all shares in the contract's withdraw queue for a backstop
+    /// of a pool that have unlocked, less the shares reserved by rebalance plans. A plan only
+    /// reserves unlocked shares once its own shares unlock. Does nothing if no unreserved shares
+    /// have unlocked.
+    ///
+    /// Returns the amount of tokens returned
+    ///
+    /// ### Arguments
+    /// * `from` - The caller of the function
+    /// * `backstop` - The address of the backstop contract
+    /// * `pool_address` - The address of the pool
+    ///
+    /// ### Errors
+    /// * WithdrawLimitExceeded - A manager would exceed the withdraw limit
+    pub fn b_withdraw_unlocked(
+        e: Env,
+        from: Address,
+        backstop: Address,
+        pool_address: Address,
+    ) -> i128 {
+        from.require_auth();
+        actions::b_withdraw_unlocked(e, from, backstop, pool_address)
+    }
+
+    /// (Manager, B_WITHDRAW and B_DEPOSIT) Migrate backstop shares of a pool from one backstop
+    /// to another. The unlocked shares are withdrawn from the old backstop, and the backstop
+    /// tokens returned are deposited into the new backstop for the same pool. The tokens do not
+    /// leave the contract's deposits, so they are not counted against the withdraw limit.
+    ///
+    /// Returns the number of backstop pool shares minted in the new backstop
+    ///
+    /// ### Arguments
+    /// * `from` - The caller of the function
+    /// * `old_backstop` - The address of the backstop contract to withdraw from
+    /// * `new_backstop` - The address of the backstop contract to deposit into
+    /// * `pool_address` - The address of the pool
+    /// * `amount` - The amount of unlocked shares to migrate
+    ///
+    /// ### Errors
+    /// * InvalidContractAddress - The pool is not paired with both backstops, or the backstops
+    ///                            are the same
+    /// * BackstopTokenMismatch - Either backstop does not use the configured backstop token
+    /// * SharesReserved - The amount includes queued shares reserved by a rebalance plan
+    pub fn b_migrate(
+        e: Env,
+        from: Address,
+        old_backstop: Address,
+        new_backstop: Address,
+        pool_address: Address,
+        amount: i128,
+    ) -> i128 {
+        from.require_auth();
+        actions::b_migrate(e, from, old_backstop, new_backstop, pool_address, amount)
+    }
+
+    /// (Manager, B_QUEUE and B_DEPOSIT) Record a plan to move backstop shares from one pool to
+    /// another, and queue the shares for withdrawal from the source pool. Once the shares unlock,
+    /// anyone can execute the plan with `execute_rebalance`.
+    ///
+    /// Returns the id of the rebalance plan
+    ///
+    /// ### Arguments
+    /// * `from` - The caller of the function
+    /// * `backstop` - The address of the backstop contract
+    /// * `source_pool` - The address of the pool to withdraw shares from
+    /// * `destination_pool` - The address of the pool to deposit the withdrawn tokens into
+    /// * `amount` - The amount of shares to move
+    ///
+    /// ### Errors
+    /// * InvalidContractAddress - Either pool is not paired with the backstop, or the pools are
+    ///                            the same
+    pub fn b_rebalance(
+        e: Env,
+        from: Address,
+        backstop: Address,
+        source_pool: Address,
+        destination_pool: Address,
+        amount: i128,
+    ) -> u32 {
+        from.require_auth();
+        actions::b_rebalance(e, from, backstop, source_pool, destination_pool, amount)
+    }
+
+    /// (Manager, B_DEQUEUE) Cancel a rebalance plan, and dequeue its shares from the withdrawal
+    /// queue of the source pool. If the shares are no longer queued, the plan is dropped and
+    /// only the shares still queued are dequeued.
+    ///
+    /// ### Arguments
+    /// * `from` - The caller of the function
+    /// * `plan_id` - The id of the rebalance plan
+    ///
+    /// ### Errors
+    /// * RebalanceNotFound - No open rebalance plan exists with the id
+    pub fn cancel_rebalance(e: Env, from: Address, plan_id: u32) {
+        from.require_auth();
+        actions::cancel_rebalance(e, from, plan_id)
     }
 
     /***** Backstop Token Interactions *****/
 
-    /// (Manager, High) Join the BLND-USDC LP.
+    /// (Manager, C_JOIN) Join the BLND-USDC LP.
     ///
     /// ### Arguments
     /// * `from` - The caller of the function
@@ -355,139 +1535,100 @@ impl BackstopManager {
     /// * `pool_amount_out` - The amount of pool shares to mint
     /// * `max_amounts_in` - The maximum amount of tokens to deposit
     pub fn c_join_pool(e: Env, from: Address, pool_amount_out: i128, max_amounts_in: Vec<i128>) {
-        require_auth_with_scope(&e, from, 2);
-        storage::extend_instance(&e);
-
-        let backstop_token = storage::get_backstop_token(&e);
-        let comet = CometClient::new(&e, &backstop_token);
-        let comet_tokens = comet.get_tokens();
-        let mut auths = vec![&e];
-        for index in 0..comet_tokens.len() {
-            let amount = max_amounts_in.get(index).unwrap_optimized();
-            let token_address = comet_tokens.get(index).unwrap_optimized();
-            let approval_ledger = (e.ledger().sequence() / 100000 + 1) * 100000;
-            auths.push_back(InvokerContractAuthEntry::Contract(SubContractInvocation {
-                context: ContractContext {
-                    contract: token_address,
-                    fn_name: Symbol::new(&e, "approve"),
-                    args: vec![
-                        &e,
-                        e.current_contract_address().into_val(&e),
-                        backstop_token.into_val(&e),
-                        amount.into_val(&e),
-                        approval_ledger.into_val(&e),
-                    ],
-                },
-                sub_invocations: vec![&e],
-            }));
-        }
-        e.authorize_as_current_contract(auths);
-        comet.join_pool(
-            &pool_amount_out,
-            &max_amounts_in,
-            &e.current_contract_address(),
-        );
+        from.require_auth();
+        actions::c_join_pool(e, from, pool_amount_out, max_amounts_in)
     }
 
-    /// (Manager, High) Exit a backstop token's liquidity pool.
+    /// (Manager, C_JOIN) Join the BLND-USDC LP with a single token
+    ///
+    /// Returns the amount of pool shares minted
+    ///
+    /// ### Arguments
+    /// * `from` - The caller of the function
+    /// * `token_index` - The index of the token to deposit in the backstop token
+    /// * `token_amount_in` - The amount of the token to deposit
+    /// * `min_pool_amount_out` - The minimum amount of pool shares to mint
+    ///
+    /// ### Errors
+    /// * InvalidTokenIndex - The token index is not a token of the backstop token
+    /// * MinTokensOutNotMet - Less pool shares were minted than the slippage floor of the token
+    pub fn c_join_single(
+        e: Env,
+        from: Address,
+        token_index: u32,
+        token_amount_in: i128,
+        min_pool_amount_out: i128,
+    ) -> i128 {
+        from.require_auth();
+        actions::c_join_single(e, from, token_index, token_amount_in, min_pool_amount_out)
+    }
+
+    /// (Manager, C_EXIT) Exit a backstop token's liquidity pool.
     ///
     /// ### Arguments
     /// * `from` - The caller of the function
     /// * `burn_amount` - The amount of pool shares to burn
     /// * `min_amounts_out` - The minimum amount of tokens to receive
+    ///
+    /// ### Errors
+    /// * ExitLimitExceeded - A manager would exceed the exit limit
     pub fn c_exit_pool(e: Env, from: Address, burn_amount: i128, min_amounts_out: Vec<i128>) {
-        require_auth_with_scope(&e, from, 2);
-        storage::extend_instance(&e);
+        from.require_auth();
+        actions::c_exit_pool(e, from, burn_amount, min_amounts_out)
+    }
 
-        let backstop_token = storage::get_backstop_token(&e);
-        let comet = CometClient::new(&e, &backstop_token);
-        comet.exit_pool(
-            &burn_amount,
-            &min_amounts_out,
-            &e.current_contract_address(),
-        )
+    /// (Manager, C_EXIT) Exit a backstop token's liquidity pool to a single token
+    ///
+    /// Returns the amount of the token received
+    ///
+    /// ### Arguments
+    /// * `from` - The caller of the function
+    /// * `token_index` - The index of the token to receive in the backstop token
+    /// * `burn_amount` - The amount of pool shares to burn
+    /// * `min_token_out` - The minimum amount of the token to receive
+    ///
+    /// ### Errors
+    /// * InvalidTokenIndex - The token index is not a token of the backstop token
+    /// * ExitLimitExceeded - A manager would exceed the exit limit
+    /// * MinTokensOutNotMet - Less of the token was received than its slippage floor
+    pub fn c_exit_single(
+        e: Env,
+        from: Address,
+        token_index: u32,
+        burn_amount: i128,
+        min_token_out: i128,
+    ) -> i128 {
+        from.require_auth();
+        actions::c_exit_single(e, from, token_index, burn_amount, min_token_out)
     }
 
     /***** Backstop Bootstrapper Interactions *****/
 
-    /// (Manager, Low) Claims the proceeds of a backstop bootstrapping
+    /// (Manager, BB_CLAIM) Claims the proceeds of a backstop bootstrapping
     ///
     /// ### Arguments
     /// * `from` - The caller of the function
     /// * `bootstrap_id` - The id of the bootstrapper
     /// * `backstop` - The address of the backstop the bootstrap is for
+    ///
+    /// ### Errors
+    /// * InvalidContractAddress - The backstop is not included in the valid list
     pub fn bb_claim_bootstrap(e: Env, from: Address, bootstrap_id: u32, backstop: Address) -> i128 {
-        require_auth_with_scope(&e, from, 0);
-        storage::extend_instance(&e);
-
-        // no need to validate backstop arg, as it's just used to pre-auth the deposit, and is never
-        // invoked directly. If an invalid arg is passed, the pre-auth will be invalid.
-
-        let backstop_bootstrapper_client =
-            BootstrapClient::new(&e, &storage::get_backstop_bootstrapper(&e));
-        let bootstrap: Bootstrap = backstop_bootstrapper_client.get_bootstrap(&bootstrap_id);
-        let comet_client = CometClient::new(&e, &storage::get_backstop_token(&e));
-
-        let backstop_token_amount = bootstrap.data.total_backstop_tokens
-            * (comet_client.get_normalized_weight(
-                &comet_client
-                    .get_tokens()
-                    .get(bootstrap.config.token_index)
-                    .unwrap_optimized(),
-            ) as i128)
-            / 1_000_0000;
-
-        e.authorize_as_current_contract(vec![
-            &e,
-            InvokerContractAuthEntry::Contract(SubContractInvocation {
-                context: ContractContext {
-                    contract: backstop.clone(),
-                    fn_name: Symbol::new(&e, "deposit"),
-                    args: vec![
-                        &e,
-                        e.current_contract_address().into_val(&e),
-                        bootstrap.config.pool.into_val(&e),
-                        backstop_token_amount.into_val(&e),
-                    ],
-                },
-                sub_invocations: vec![
-                    &e,
-                    InvokerContractAuthEntry::Contract(SubContractInvocation {
-                        context: ContractContext {
-                            contract: comet_client.address,
-                            fn_name: Symbol::new(&e, "transfer"),
-                            args: vec![
-                                &e,
-                                e.current_contract_address().into_val(&e),
-                                backstop.into_val(&e),
-                                backstop_token_amount.into_val(&e),
-                            ],
-                        },
-                        sub_invocations: vec![&e],
-                    }),
-                ],
-            }),
-        ]);
-
-        backstop_bootstrapper_client.claim(&e.current_contract_address(), &bootstrap_id)
+        from.require_auth();
+        actions::bb_claim_bootstrap(e, from, bootstrap_id, backstop)
     }
 
-    /// (Manager, Low) Refunds a cancelled backstop bootstrapping
+    /// (Manager, BB_REFUND) Refunds a cancelled backstop bootstrapping
     ///
     /// ### Arguments
     /// * `from` - The caller of the function
     /// * `bootstrap_id` - The id of the bootstrapper
     pub fn bb_refund_bootstrap(e: Env, from: Address, bootstrap_id: u32) -> i128 {
-        require_auth_with_scope(&e, from, 0);
-        storage::extend_instance(&e);
-
-        let backstop_bootstrapper_client =
-            BootstrapClient::new(&e, &storage::get_backstop_bootstrapper(&e));
-
-        backstop_bootstrapper_client.refund(&e.current_contract_address(), &bootstrap_id)
+        from.require_auth();
+        actions::bb_refund_bootstrap(e, from, bootstrap_id)
     }
 
-    /// (Manager, High) Creates a Backstop Bootstrapping with BLND
+    /// (Manager, BB_START) Creates a Backstop Bootstrapping with BLND
     ///
     /// ### Arguments
     /// * `from` - The caller of the function
@@ -496,6 +1637,10 @@ impl BackstopManager {
     /// * `pair_min` - The minimum amount of pool shares to mint
     /// * `duration` - The duration of the bootstrapping period
     /// * `pool_address` - The address of the pool
+    ///
+    /// ### Errors
+    /// * InvalidContractAddress - The pool is not included in the valid list
+    /// * InvalidTokenIndex - The bootstrap token index is not a token of the backstop token
     pub fn bb_start_bootstrap(
         e: Env,
         from: Address,
@@ -505,82 +1650,262 @@ impl BackstopManager {
         duration: u32,
         pool_address: Address,
     ) -> u32 {
-        require_auth_with_scope(&e, from, 2);
-        storage::extend_instance(&e);
-
-        let backstop_token = storage::get_backstop_token(&e);
-        let bootstrap_token: Address = match CometClient::new(&e, &backstop_token)
-            .get_tokens()
-            .get(bootstrap_token_index)
-        {
-            Some(address) => address,
-            None => panic_with_error!(e, BackstopManagerErrors::InvalidTokenIndex),
-        };
-
-        let backstop_bootstrapper = storage::get_backstop_bootstrapper(&e);
-        e.authorize_as_current_contract(vec![
-            &e,
-            InvokerContractAuthEntry::Contract(SubContractInvocation {
-                context: ContractContext {
-                    contract: bootstrap_token,
-                    fn_name: Symbol::new(&e, "transfer"),
-                    args: vec![
-                        &e,
-                        e.current_contract_address().into_val(&e),
-                        backstop_bootstrapper.into_val(&e),
-                        bootstrap_amount.into_val(&e),
-                    ],
-                },
-                sub_invocations: vec![&e],
-            }),
-        ]);
-
-        BootstrapClient::new(&e, &backstop_bootstrapper).bootstrap(&BootstrapConfig {
-            bootstrapper: e.current_contract_address(),
-            amount: bootstrap_amount,
-            close_ledger: e.ledger().sequence() + duration,
+        from.require_auth();
+        actions::bb_start_bootstrap(
+            e,
+            from,
+            bootstrap_token_index,
+            bootstrap_amount,
             pair_min,
-            pool: pool_address,
-            token_index: bootstrap_token_index,
-        })
+            duration,
+            pool_address,
+        )
+    }
+}
+
+/// Load the backstop deposit held by the contract for a backstop and pool pair
+fn load_position(e: &Env, pair: Pair) -> Position {
+    let backstop_client = BackstopClient::new(e, &pair.backstop);
+    let balance = backstop_client.user_balance(&pair.pool, &e.current_contract_address());
+    let pool_data = backstop_client.pool_data(&pair.pool);
+    let mut q4w = Vec::new(e);
+    let mut q4w_shares = 0;
+    for entry in balance.q4w.iter() {
+        q4w_shares += entry.amount;
+        q4w.push_back(QueuedWithdrawal {
+            amount: entry.amount,
+            exp: entry.exp,
+        });
+    }
+    Position {
+        backstop: pair.backstop,
+        pool: pair.pool,
+        shares: balance.shares,
+        tokens: shares_to_tokens(balance.shares, pool_data.shares, pool_data.tokens),
+        q4w,
+        q4w_tokens: shares_to_tokens(q4w_shares, pool_data.shares, pool_data.tokens),
     }
 }
 
-/// Authorize an action based on a provide scope for from. If `from` is the owner,
-/// then the action is authorized. If `from` is the manager, then the manager is validated
-/// to have the appropriate scope.
+/// Convert backstop pool shares to backstop tokens, rounding down
 ///
-/// THIS CALLS REQUIRE AUTH FOR FROM
+/// ### Arguments
+/// * `shares` - The amount of shares to convert
+/// * `total_shares` - The total shares of the backstop pool
+/// * `total_tokens` - The total backstop tokens of the backstop pool
+fn shares_to_tokens(shares: i128, total_shares: i128, total_tokens: i128) -> i128 {
+    if total_shares == 0 {
+        return 0;
+    }
+    shares * total_tokens / total_shares
+}
+
+/// Apply an owner configuration change immediately if no timelock is set, otherwise
+/// queue it to be executed once the timelock has passed
 ///
 /// ### Arguments
-/// * `from` - The address of the caller
-/// * `scope` - The scope required for the action
+/// * `change` - The configuration change
 ///
 /// ### Errors
-/// * UnauthorizedError - The caller is not authorized to perform the action
-fn require_auth_with_scope(e: &Env, from: Address, scope: u32) {
-    from.require_auth();
-    if from == storage::get_owner(&e) {
+/// * ContractListOverMax - The maximum number of changes are already pending
+fn queue_or_apply_change(e: &Env, change: ConfigChange) {
+    let timelock = storage::get_timelock(e);
+    if timelock == 0 {
+        apply_config_change(e, change);
         return;
     }
-    let manager = storage::get_manager(&e);
-    if manager.id != from || manager.scope < scope {
-        panic_with_error!(&e, BackstopManagerErrors::UnauthorizedError);
+
+    let mut pending_changes = storage::get_pending_changes(e);
+    if pending_changes.len() >= MAX_PENDING_CHANGES {
+        panic_with_error!(e, BackstopManagerErrors::ContractListOverMax);
+    }
+    let id = storage::get_next_pending_change_id(e);
+    let pending_change = PendingChange {
+        change,
+        execute_after: e.ledger().sequence() + timelock,
+    };
+    pending_changes.set(id, pending_change.clone());
+    storage::set_pending_changes(e, &pending_changes);
+    storage::set_next_pending_change_id(e, id + 1);
+    BackstopManagerEvents::queue_change(e, storage::get_owner(e), id, pending_change);
+}
+
+/// Apply an owner configuration change. Validation that depends on contract state
+/// is done here, as state can change while a change is pending.
+///
+/// ### Arguments
+/// * `change` - The configuration change
+///
+/// ### Errors
+/// * ManagerAlreadyExists - The address is already a manager
+/// * ManagerNotFound - The address is not a manager
+/// * ContractListOverMax - The contract already has the maximum number of managers
+/// * InvalidExpiration - The expiration ledger is not in the future
+/// * InvalidLimit - The limit being removed is not set
+fn apply_config_change(e: &Env, change: ConfigChange) {
+    let owner = storage::get_owner(e);
+    match change {
+        ConfigChange::AddManager(manager, manager_permissions, expires_at_ledger) => {
+            require_manager_expiration_valid(e, expires_at_ledger);
+            let mut managers = storage::get_managers(e);
+            if managers.contains_key(manager.clone()) {
+                panic_with_error!(e, BackstopManagerErrors::ManagerAlreadyExists);
+            }
+            if managers.len() >= MAX_MANAGERS {
+                panic_with_error!(e, BackstopManagerErrors::ContractListOverMax);
+            }
+            managers.set(
+                manager.clone(),
+                ManagerConfig {
+                    permissions: manager_permissions,
+                    expires_at_ledger,
+                },
+            );
+            storage::set_managers(e, &managers);
+            BackstopManagerEvents::add_manager(
+                e,
+                owner,
+                manager,
+                manager_permissions,
+                expires_at_ledger,
+            );
+        }
+        ConfigChange::SetManagerPermissions(manager, manager_permissions) => {
+            let mut managers = storage::get_managers(e);
+            let mut manager_config = match managers.get(manager.clone()) {
+                Some(manager_config) => manager_config,
+                None => panic_with_error!(e, BackstopManagerErrors::ManagerNotFound),
+            };
+            let old_permissions = manager_config.permissions;
+            manager_config.permissions = manager_permissions;
+            managers.set(manager.clone(), manager_config);
+            storage::set_managers(e, &managers);
+            BackstopManagerEvents::update_manager(
+                e,
+                owner,
+                manager,
+                old_permissions,
+                manager_permissions,
+            );
+        }
+        ConfigChange::RenewManager(manager, expires_at_ledger) => {
+            require_manager_expiration_valid(e, expires_at_ledger);
+            let mut managers = storage::get_managers(e);
+            let mut manager_config = match managers.get(manager.clone()) {
+                Some(manager_config) => manager_config,
+                None => panic_with_error!(e, BackstopManagerErrors::ManagerNotFound),
+            };
+            let old_expires_at_ledger = manager_config.expires_at_ledger;
+            manager_config.expires_at_ledger = expires_at_ledger;
+            managers.set(manager.clone(), manager_config);
+            storage::set_managers(e, &managers);
+            BackstopManagerEvents::renew_manager(
+                e,
+                owner,
+                manager,
+                old_expires_at_ledger,
+                expires_at_ledger,
+            );
+        }
+        ConfigChange::SetBackstopBootstrapper(bootstrapper) => {
+            storage::set_backstop_bootstrapper(e, bootstrapper.clone());
+            BackstopManagerEvents::set_backstop_bootstrapper(e, owner, bootstrapper);
+        }
+        ConfigChange::AddBackstop(backstop) => {
+            if !storage::add_valid_backstop(e, &backstop) {
+                panic_with_error!(e, BackstopManagerErrors::AddressAlreadyListed);
+            }
+            BackstopManagerEvents::add_backstop(e, owner, backstop);
+        }
+        ConfigChange::AddPool(pool) => {
+            if !storage::add_valid_pool(e, &pool) {
+                panic_with_error!(e, BackstopManagerErrors::AddressAlreadyListed);
+            }
+            BackstopManagerEvents::add_pool(e, owner, pool);
+        }
+        ConfigChange::AddPair(pair) => {
+            if !storage::is_valid_backstop(e, &pair.backstop)
+                || !storage::is_valid_pool(e, &pair.pool)
+            {
+                panic_with_error!(e, BackstopManagerErrors::InvalidContractAddress);
+            }
+            if !storage::add_pair(e, &pair) {
+                panic_with_error!(e, BackstopManagerErrors::PairAlreadyExists);
+            }
+            BackstopManagerEvents::add_pair(e, owner, pair);
+        }
+        ConfigChange::SetTimelock(timelock) => {
+            let old_timelock = storage::get_timelock(e);
+            storage::set_timelock(e, timelock);
+            BackstopManagerEvents::set_timelock(e, owner, old_timelock, timelock);
+        }
+        ConfigChange::SetGuardian(guardian) => {
+            match guardian.clone() {
+                Some(guardian) => storage::set_guardian(e, &guardian),
+                None => storage::del_guardian(e),
+            }
+            BackstopManagerEvents::set_guardian(e, owner, guardian);
+        }
+        ConfigChange::SetLimit(key, max_amount, period) => {
+            let mut limits = storage::get_limits(e);
+            limits.set(key.clone(), Limit { max_amount, period });
+            storage::set_limits(e, &limits);
+            BackstopManagerEvents::set_limit(e, owner, key, max_amount, period);
+        }
+        ConfigChange::RemoveLimit(key) => {
+            let mut limits = storage::get_limits(e);
+            if limits.remove(key.clone()).is_none() {
+                panic_with_error!(e, BackstopManagerErrors::InvalidLimit);
+            }
+            storage::set_limits(e, &limits);
+            let mut limit_usage = storage::get_limit_usage(e);
+            limit_usage.remove(key.clone());
+            storage::set_limit_usage(e, &limit_usage);
+            BackstopManagerEvents::remove_limit(e, owner, key);
+        }
+        ConfigChange::SetSlippageFloor(token, floor) => {
+            let mut floors = storage::get_slippage_floors(e);
+            floors.set(token.clone(), floor.clone());
+            storage::set_slippage_floors(e, &floors);
+            BackstopManagerEvents::set_slippage_floor(e, owner, token, floor);
+        }
+        ConfigChange::Upgrade(new_wasm_hash) => {
+            e.deployer()
+                .update_current_contract_wasm(new_wasm_hash.clone());
+            BackstopManagerEvents::upgrade(e, owner, new_wasm_hash);
+        }
+        ConfigChange::SetKeeper(config) => {
+            storage::set_keeper(e, &config);
+            BackstopManagerEvents::set_keeper(e, owner, config);
+        }
+    }
+}
+
+/// Validate a manager expiration, if one is set, is in the future
+///
+/// ### Arguments
+/// * `expires_at_ledger` - The last ledger the manager can act, or None if the manager does not expire
+///
+/// ### Errors
+/// * InvalidExpiration - The expiration ledger is not in the future
+fn require_manager_expiration_valid(e: &Env, expires_at_ledger: Option<u32>) {
+    if let Some(expires_at_ledger) = expires_at_ledger {
+        if expires_at_ledger <= e.ledger().sequence() {
+            panic_with_error!(e, BackstopManagerErrors::InvalidExpiration);
+        }
     }
 }
 
-/// Validate that the backstop and pool address are included in the valid lists
+/// Expand a scope into its preset permissions
 ///
 /// ### Arguments
-/// * `backstop` - The address of the backstop contract
-/// * `pool_address` - The address of the pool
+/// * `scope` - The scope. 0 = Low, 1 = Medium, 2 = High
 ///
 /// ### Errors
-/// * InvalidContractAddress - The backstop or pool address is not included in the valid lists
-fn require_backstop_and_pool_valid(e: &Env, backstop: &Address, pool_address: &Address) {
-    let backstops = storage::get_valid_backstops(&e);
-    let pools = storage::get_valid_pools(&e);
-    if !backstops.contains(backstop) || !pools.contains(pool_address) {
-        panic_with_error!(&e, BackstopManagerErrors::InvalidContractAddress);
+/// * InvalidScope - The scope is not valid
+fn require_scope_permissions(e: &Env, scope: u32) -> u32 {
+    match permissions::from_scope(scope) {
+        Some(manager_permissions) => manager_permissions,
+        None => panic_with_error!(e, BackstopManagerErrors::InvalidScope),
     }
 }
@@ -19,4 +19,34 @@ pub enum BackstopManagerErrors {
     InvalidContractAddress = 101,
     InvalidScope = 102,
     InvalidTokenIndex = 103,
+    InvalidExpiration = 104,
+    NoPendingOwner = 105,
+    PendingOwnerExpired = 106,
+    ManagerAlreadyExists = 107,
+    ManagerNotFound = 108,
+    InvalidPermissions = 109,
+    ManagerExpired = 110,
+    InvalidLimit = 111,
+    WithdrawLimitExceeded = 112,
+    ExitLimitExceeded = 113,
+    RefundLimitExceeded = 114,
+    ContractPaused = 115,
+    PendingChangeNotFound = 116,
+    PendingChangeLocked = 117,
+    InvalidTimelock = 118,
+    InvalidSchemaVersion = 119,
+    PairAlreadyExists = 120,
+    PairNotFound = 121,
+    AddressAlreadyListed = 122,
+    AddressNotListed = 123,
+    InvalidHistoryRetention = 124,
+    MinTokensOutNotMet = 125,
+    InvalidKeeperConfig = 126,
+    KeeperDisabled = 127,
+    KeeperIntervalNotPassed = 128,
+    RebalanceNotFound = 129,
+    RebalanceLocked = 130,
+    BackstopTokenMismatch = 131,
+    SharesReserved = 132,
+    InvalidSlippageFloor = 133,
 }
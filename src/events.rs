@@ -0,0 +1,598 @@
+use soroban_sdk::{Address, BytesN, Env, Symbol, Vec};
+
+use crate::storage::{KeeperConfig, LimitKey, Pair, PendingChange, RebalancePlan, SlippageFloor};
+
+pub struct BackstopManagerEvents {}
+
+impl BackstopManagerEvents {
+    /********** Owner **********/
+
+    /// Emitted when the owner transfers tokens held by the contract
+    ///
+    /// - topics - `["transfer_token", owner: Address]`
+    /// - data - `[token: Address, to: Address, amount: i128]`
+    pub fn transfer_token(e: &Env, owner: Address, token: Address, to: Address, amount: i128) {
+        let topics = (Symbol::new(e, "transfer_token"), owner);
+        e.events().publish(topics, (token, to, amount));
+    }
+
+    /// Emitted when the owner proposes a new owner
+    ///
+    /// - topics - `["propose_owner", owner: Address]`
+    /// - data - `[new_owner: Address, expiration_ledger: u32]`
+    pub fn propose_owner(e: &Env, owner: Address, new_owner: Address, expiration_ledger: u32) {
+        let topics = (Symbol::new(e, "propose_owner"), owner);
+        e.events().publish(topics, (new_owner, expiration_ledger));
+    }
+
+    /// Emitted when the owner cancels a proposed ownership transfer
+    ///
+    /// - topics - `["cancel_owner_transfer", owner: Address]`
+    /// - data - `pending_owner: Address`
+    pub fn cancel_owner_transfer(e: &Env, owner: Address, pending_owner: Address) {
+        let topics = (Symbol::new(e, "cancel_owner_transfer"), owner);
+        e.events().publish(topics, pending_owner);
+    }
+
+    /// Emitted when the pending owner accepts ownership
+    ///
+    /// - topics - `["accept_owner", new_owner: Address]`
+    /// - data - `old_owner: Address`
+    pub fn accept_owner(e: &Env, new_owner: Address, old_owner: Address) {
+        let topics = (Symbol::new(e, "accept_owner"), new_owner);
+        e.events().publish(topics, old_owner);
+    }
+
+    /// Emitted when a configuration change is queued by the timelock
+    ///
+    /// - topics - `["queue_change", owner: Address]`
+    /// - data - `[id: u32, pending_change: PendingChange]`
+    pub fn queue_change(e: &Env, owner: Address, id: u32, pending_change: PendingChange) {
+        let topics = (Symbol::new(e, "queue_change"), owner);
+        e.events().publish(topics, (id, pending_change));
+    }
+
+    /// Emitted when the owner cancels a queued configuration change
+    ///
+    /// - topics - `["cancel_pending", owner: Address]`
+    /// - data - `id: u32`
+    pub fn cancel_pending(e: &Env, owner: Address, id: u32) {
+        let topics = (Symbol::new(e, "cancel_pending"), owner);
+        e.events().publish(topics, id);
+    }
+
+    /// Emitted when a manager is added
+    ///
+    /// - topics - `["add_manager", owner: Address]`
+    /// - data - `[manager: Address, permissions: u32, expires_at_ledger: Option<u32>]`
+    pub fn add_manager(
+        e: &Env,
+        owner: Address,
+        manager: Address,
+        permissions: u32,
+        expires_at_ledger: Option<u32>,
+    ) {
+        let topics = (Symbol::new(e, "add_manager"), owner);
+        e.events()
+            .publish(topics, (manager, permissions, expires_at_ledger));
+    }
+
+    /// Emitted when the permissions of a manager are updated
+    ///
+    /// - topics - `["update_manager", owner: Address]`
+    /// - data - `[manager: Address, old_permissions: u32, new_permissions: u32]`
+    pub fn update_manager(
+        e: &Env,
+        owner: Address,
+        manager: Address,
+        old_permissions: u32,
+        new_permissions: u32,
+    ) {
+        let topics = (Symbol::new(e, "update_manager"), owner);
+        e.events()
+            .publish(topics, (manager, old_permissions, new_permissions));
+    }
+
+    /// Emitted when a manager is renewed
+    ///
+    /// - topics - `["renew_manager", owner: Address]`
+    /// - data - `[manager: Address, old_expires_at_ledger: Option<u32>, new_expires_at_ledger: Option<u32>]`
+    pub fn renew_manager(
+        e: &Env,
+        owner: Address,
+        manager: Address,
+        old_expires_at_ledger: Option<u32>,
+        new_expires_at_ledger: Option<u32>,
+    ) {
+        let topics = (Symbol::new(e, "renew_manager"), owner);
+        e.events().publish(
+            topics,
+            (manager, old_expires_at_ledger, new_expires_at_ledger),
+        );
+    }
+
+    /// Emitted when a manager is removed
+    ///
+    /// - topics - `["remove_manager", owner: Address]`
+    /// - data - `manager: Address`
+    pub fn remove_manager(e: &Env, owner: Address, manager: Address) {
+        let topics = (Symbol::new(e, "remove_manager"), owner);
+        e.events().publish(topics, manager);
+    }
+
+    /// Emitted when the backstop bootstrapper is set
+    ///
+    /// - topics - `["set_backstop_bootstrapper", owner: Address]`
+    /// - data - `bootstrapper: Address`
+    pub fn set_backstop_bootstrapper(e: &Env, owner: Address, bootstrapper: Address) {
+        let topics = (Symbol::new(e, "set_backstop_bootstrapper"), owner);
+        e.events().publish(topics, bootstrapper);
+    }
+
+    /// Emitted when a valid backstop is added
+    ///
+    /// - topics - `["add_backstop", owner: Address]`
+    /// - data - `backstop: Address`
+    pub fn add_backstop(e: &Env, owner: Address, backstop: Address) {
+        let topics = (Symbol::new(e, "add_backstop"), owner);
+        e.events().publish(topics, backstop);
+    }
+
+    /// Emitted when a valid backstop is removed
+    ///
+    /// - topics - `["remove_backstop", owner: Address]`
+    /// - data - `backstop: Address`
+    pub fn remove_backstop(e: &Env, owner: Address, backstop: Address) {
+        let topics = (Symbol::new(e, "remove_backstop"), owner);
+        e.events().publish(topics, backstop);
+    }
+
+    /// Emitted when a valid pool is added
+    ///
+    /// - topics - `["add_pool", owner: Address]`
+    /// - data - `pool: Address`
+    pub fn add_pool(e: &Env, owner: Address, pool: Address) {
+        let topics = (Symbol::new(e, "add_pool"), owner);
+        e.events().publish(topics, pool);
+    }
+
+    /// Emitted when a valid pool is removed
+    ///
+    /// - topics - `["remove_pool", owner: Address]`
+    /// - data - `pool: Address`
+    pub fn remove_pool(e: &Env, owner: Address, pool: Address) {
+        let topics = (Symbol::new(e, "remove_pool"), owner);
+        e.events().publish(topics, pool);
+    }
+
+    /// Emitted when a backstop and pool pair is added
+    ///
+    /// - topics - `["add_pair", owner: Address]`
+    /// - data - `pair: Pair`
+    pub fn add_pair(e: &Env, owner: Address, pair: Pair) {
+        let topics = (Symbol::new(e, "add_pair"), owner);
+        e.events().publish(topics, pair);
+    }
+
+    /// Emitted when a backstop and pool pair is removed
+    ///
+    /// - topics - `["remove_pair", owner: Address]`
+    /// - data - `pair: Pair`
+    pub fn remove_pair(e: &Env, owner: Address, pair: Pair) {
+        let topics = (Symbol::new(e, "remove_pair"), owner);
+        e.events().publish(topics, pair);
+    }
+
+    /// Emitted when the timelock is set
+    ///
+    /// - topics - `["set_timelock", owner: Address]`
+    /// - data - `[old_timelock: u32, new_timelock: u32]`
+    pub fn set_timelock(e: &Env, owner: Address, old_timelock: u32, new_timelock: u32) {
+        let topics = (Symbol::new(e, "set_timelock"), owner);
+        e.events().publish(topics, (old_timelock, new_timelock));
+    }
+
+    /// Emitted when keepers are enabled, or their bounds are updated
+    ///
+    /// - topics - `["set_keeper", owner: Address]`
+    /// - data - `config: KeeperConfig`
+    pub fn set_keeper(e: &Env, owner: Address, config: KeeperConfig) {
+        let topics = (Symbol::new(e, "set_keeper"), owner);
+        e.events().publish(topics, config);
+    }
+
+    /// Emitted when the owner funds keeper bounties
+    ///
+    /// - topics - `["fund_keeper", owner: Address]`
+    /// - data - `[amount: i128, budget: i128]`
+    pub fn fund_keeper(e: &Env, owner: Address, amount: i128, budget: i128) {
+        let topics = (Symbol::new(e, "fund_keeper"), owner);
+        e.events().publish(topics, (amount, budget));
+    }
+
+    /// Emitted when keepers are disabled
+    ///
+    /// - topics - `["remove_keeper", owner: Address]`
+    /// - data - `()`
+    pub fn remove_keeper(e: &Env, owner: Address) {
+        let topics = (Symbol::new(e, "remove_keeper"), owner);
+        e.events().publish(topics, ());
+    }
+
+    /// Emitted when the contract is upgraded
+    ///
+    /// - topics - `["upgrade", owner: Address]`
+    /// - data - `new_wasm_hash: BytesN<32>`
+    pub fn upgrade(e: &Env, owner: Address, new_wasm_hash: BytesN<32>) {
+        let topics = (Symbol::new(e, "upgrade"), owner);
+        e.events().publish(topics, new_wasm_hash);
+    }
+
+    /// Emitted when the contract storage is migrated
+    ///
+    /// - topics - `["migrate", owner: Address]`
+    /// - data - `[old_version: u32, new_version: u32]`
+    pub fn migrate(e: &Env, owner: Address, old_version: u32, new_version: u32) {
+        let topics = (Symbol::new(e, "migrate"), owner);
+        e.events().publish(topics, (old_version, new_version));
+    }
+
+    /// Emitted when the guardian is set or removed
+    ///
+    /// - topics - `["set_guardian", owner: Address]`
+    /// - data - `guardian: Option<Address>`
+    pub fn set_guardian(e: &Env, owner: Address, guardian: Option<Address>) {
+        let topics = (Symbol::new(e, "set_guardian"), owner);
+        e.events().publish(topics, guardian);
+    }
+
+    /// Emitted when the owner unpauses manager actions
+    ///
+    /// - topics - `["unpause", owner: Address]`
+    /// - data - `()`
+    pub fn unpause(e: &Env, owner: Address) {
+        let topics = (Symbol::new(e, "unpause"), owner);
+        e.events().publish(topics, ());
+    }
+
+    /// Emitted when a limit is set
+    ///
+    /// - topics - `["set_limit", owner: Address]`
+    /// - data - `[key: LimitKey, max_amount: i128, period: u32]`
+    pub fn set_limit(e: &Env, owner: Address, key: LimitKey, max_amount: i128, period: u32) {
+        let topics = (Symbol::new(e, "set_limit"), owner);
+        e.events().publish(topics, (key, max_amount, period));
+    }
+
+    /// Emitted when the slippage floor of single-sided joins and exits with a token is set
+    ///
+    /// - topics - `["set_slippage_floor", owner: Address]`
+    /// - data - `[token: Address, floor: SlippageFloor]`
+    pub fn set_slippage_floor(e: &Env, owner: Address, token: Address, floor: SlippageFloor) {
+        let topics = (Symbol::new(e, "set_slippage_floor"), owner);
+        e.events().publish(topics, (token, floor));
+    }
+
+    /// Emitted when a limit is removed
+    ///
+    /// - topics - `["remove_limit", owner: Address]`
+    /// - data - `key: LimitKey`
+    pub fn remove_limit(e: &Env, owner: Address, key: LimitKey) {
+        let topics = (Symbol::new(e, "remove_limit"), owner);
+        e.events().publish(topics, key);
+    }
+
+    /// Emitted when the number of actions kept in the history is set
+    ///
+    /// - topics - `["set_history_retention", owner: Address]`
+    /// - data - `[old_retention: u32, new_retention: u32]`
+    pub fn set_history_retention(e: &Env, owner: Address, old_retention: u32, new_retention: u32) {
+        let topics = (Symbol::new(e, "set_history_retention"), owner);
+        e.events().publish(topics, (old_retention, new_retention));
+    }
+
+    /// Emitted when the claimed totals of a backstop and pool pair are snapshotted
+    ///
+    /// - topics - `["snapshot_claimed", owner: Address]`
+    /// - data - `[backstop: Address, pool: Address, amount: i128, blnd: i128]`
+    pub fn snapshot_claimed(
+        e: &Env,
+        owner: Address,
+        backstop: Address,
+        pool: Address,
+        amount: i128,
+        blnd: i128,
+    ) {
+        let topics = (Symbol::new(e, "snapshot_claimed"), owner);
+        e.events().publish(topics, (backstop, pool, amount, blnd));
+    }
+
+    /********** Guardian **********/
+
+    /// Emitted when the guardian pauses manager actions
+    ///
+    /// - topics - `["pause", guardian: Address]`
+    /// - data - `()`
+    pub fn pause(e: &Env, guardian: Address) {
+        let topics = (Symbol::new(e, "pause"), guardian);
+        e.events().publish(topics, ());
+    }
+
+    /********** Permissionless **********/
+
+    /// Emitted when a keeper compounds backstop emissions for a pool
+    ///
+    /// - topics - `["keeper_compound", keeper: Address]`
+    /// - data - `[backstop: Address, pool: Address, claimed: i128, bounty: i128]`
+    pub fn keeper_compound(
+        e: &Env,
+        keeper: Address,
+        backstop: Address,
+        pool: Address,
+        claimed: i128,
+        bounty: i128,
+    ) {
+        let topics = (Symbol::new(e, "keeper_compound"), keeper);
+        e.events()
+            .publish(topics, (backstop, pool, claimed, bounty));
+    }
+
+    /// Emitted when a rebalance plan is executed
+    ///
+    /// - topics - `["execute_rebalance", from: Address]`
+    /// - data - `[plan_id: u32, tokens: i128, shares: i128]`
+    pub fn execute_rebalance(e: &Env, from: Address, plan_id: u32, tokens: i128, shares: i128) {
+        let topics = (Symbol::new(e, "execute_rebalance"), from);
+        e.events().publish(topics, (plan_id, tokens, shares));
+    }
+
+    /********** Manager **********/
+
+    /// Emitted when tokens are refunded to the owner
+    ///
+    /// - topics - `["refund_token", from: Address]`
+    /// - data - `[token: Address, amount: i128]`
+    pub fn refund_token(e: &Env, from: Address, token: Address, amount: i128) {
+        let topics = (Symbol::new(e, "refund_token"), from);
+        e.events().publish(topics, (token, amount));
+    }
+
+    /// Emitted when backstop emissions are claimed
+    ///
+    /// - topics - `["b_claim", from: Address]`
+    /// - data - `[backstop: Address, pool: Address, claimed: i128]`
+    pub fn b_claim(e: &Env, from: Address, backstop: Address, pool: Address, claimed: i128) {
+        let topics = (Symbol::new(e, "b_claim"), from);
+        e.events().publish(topics, (backstop, pool, claimed));
+    }
+
+    /// Emitted when backstop emissions from a list of pools are compounded
+    ///
+    /// - topics - `["b_compound", from: Address]`
+    /// - data - `[backstop: Address, pools: Vec<Address>, claimed: i128]`
+    pub fn b_compound(
+        e: &Env,
+        from: Address,
+        backstop: Address,
+        pools: Vec<Address>,
+        claimed: i128,
+    ) {
+        let topics = (Symbol::new(e, "b_compound"), from);
+        e.events().publish(topics, (backstop, pools, claimed));
+    }
+
+    /// Emitted when backstop tokens are deposited into a backstop
+    ///
+    /// - topics - `["b_deposit", from: Address]`
+    /// - data - `[backstop: Address, pool: Address, amount: i128, shares: i128]`
+    pub fn b_deposit(
+        e: &Env,
+        from: Address,
+        backstop: Address,
+        pool: Address,
+        amount: i128,
+        shares: i128,
+    ) {
+        let topics = (Symbol::new(e, "b_deposit"), from);
+        e.events().publish(topics, (backstop, pool, amount, shares));
+    }
+
+    /// Emitted when backstop shares are queued for withdrawal
+    ///
+    /// - topics - `["b_queue_withdrawal", from: Address]`
+    /// - data - `[backstop: Address, pool: Address, amount: i128]`
+    pub fn b_queue_withdrawal(
+        e: &Env,
+        from: Address,
+        backstop: Address,
+        pool: Address,
+        amount: i128,
+    ) {
+        let topics = (Symbol::new(e, "b_queue_withdrawal"), from);
+        e.events().publish(topics, (backstop, pool, amount));
+    }
+
+    /// Emitted when queued backstop shares are dequeued
+    ///
+    /// - topics - `["b_dequeue_withdrawal", from: Address]`
+    /// - data - `[backstop: Address, pool: Address, amount: i128]`
+    pub fn b_dequeue_withdrawal(
+        e: &Env,
+        from: Address,
+        backstop: Address,
+        pool: Address,
+        amount: i128,
+    ) {
+        let topics = (Symbol::new(e, "b_dequeue_withdrawal"), from);
+        e.events().publish(topics, (backstop, pool, amount));
+    }
+
+    /// Emitted when backstop shares are withdrawn
+    ///
+    /// - topics - `["b_withdraw", from: Address]`
+    /// - data - `[backstop: Address, pool: Address, amount: i128, tokens_out: i128]`
+    pub fn b_withdraw(
+        e: &Env,
+        from: Address,
+        backstop: Address,
+        pool: Address,
+        amount: i128,
+        tokens_out: i128,
+    ) {
+        let topics = (Symbol::new(e, "b_withdraw"), from);
+        e.events()
+            .publish(topics, (backstop, pool, amount, tokens_out));
+    }
+
+    /// Emitted when all unlocked backstop shares are withdrawn
+    ///
+    /// - topics - `["b_withdraw_unlocked", from: Address]`
+    /// - data - `[backstop: Address, pool: Address, amount: i128, tokens_out: i128]`
+    pub fn b_withdraw_unlocked(
+        e: &Env,
+        from: Address,
+        backstop: Address,
+        pool: Address,
+        amount: i128,
+        tokens_out: i128,
+    ) {
+        let topics = (Symbol::new(e, "b_withdraw_unlocked"), from);
+        e.events()
+            .publish(topics, (backstop, pool, amount, tokens_out));
+    }
+
+    /// Emitted when backstop shares are migrated from one backstop to another
+    ///
+    /// - topics - `["b_migrate", from: Address]`
+    /// - data - `[old_backstop: Address, new_backstop: Address, pool: Address, amount: i128, tokens: i128, shares: i128]`
+    #[allow(clippy::too_many_arguments)]
+    pub fn b_migrate(
+        e: &Env,
+        from: Address,
+        old_backstop: Address,
+        new_backstop: Address,
+        pool: Address,
+        amount: i128,
+        tokens: i128,
+        shares: i128,
+    ) {
+        let topics = (Symbol::new(e, "b_migrate"), from);
+        e.events().publish(
+            topics,
+            (old_backstop, new_backstop, pool, amount, tokens, shares),
+        );
+    }
+
+    /// Emitted when a rebalance plan is recorded and its shares are queued for withdrawal
+    ///
+    /// - topics - `["b_rebalance", from: Address]`
+    /// - data - `plan: RebalancePlan`
+    pub fn b_rebalance(e: &Env, from: Address, plan: RebalancePlan) {
+        let topics = (Symbol::new(e, "b_rebalance"), from);
+        e.events().publish(topics, plan);
+    }
+
+    /// Emitted when a rebalance plan is cancelled and its shares are dequeued
+    ///
+    /// - topics - `["cancel_rebalance", from: Address]`
+    /// - data - `plan: RebalancePlan`
+    pub fn cancel_rebalance(e: &Env, from: Address, plan: RebalancePlan) {
+        let topics = (Symbol::new(e, "cancel_rebalance"), from);
+        e.events().publish(topics, plan);
+    }
+
+    /// Emitted when backstop tokens are minted by joining the comet pool
+    ///
+    /// - topics - `["c_join_pool", from: Address]`
+    /// - data - `[pool_amount_out: i128, max_amounts_in: Vec<i128>]`
+    pub fn c_join_pool(e: &Env, from: Address, pool_amount_out: i128, max_amounts_in: Vec<i128>) {
+        let topics = (Symbol::new(e, "c_join_pool"), from);
+        e.events()
+            .publish(topics, (pool_amount_out, max_amounts_in));
+    }
+
+    /// Emitted when backstop tokens are minted by joining the comet pool with a single token
+    ///
+    /// - topics - `["c_join_single", from: Address]`
+    /// - data - `[token_index: u32, token_amount_in: i128, pool_amount_out: i128]`
+    pub fn c_join_single(
+        e: &Env,
+        from: Address,
+        token_index: u32,
+        token_amount_in: i128,
+        pool_amount_out: i128,
+    ) {
+        let topics = (Symbol::new(e, "c_join_single"), from);
+        e.events()
+            .publish(topics, (token_index, token_amount_in, pool_amount_out));
+    }
+
+    /// Emitted when backstop tokens are burned by exiting the comet pool
+    ///
+    /// - topics - `["c_exit_pool", from: Address]`
+    /// - data - `[burn_amount: i128, min_amounts_out: Vec<i128>]`
+    pub fn c_exit_pool(e: &Env, from: Address, burn_amount: i128, min_amounts_out: Vec<i128>) {
+        let topics = (Symbol::new(e, "c_exit_pool"), from);
+        e.events().publish(topics, (burn_amount, min_amounts_out));
+    }
+
+    /// Emitted when backstop tokens are burned by exiting the comet pool to a single token
+    ///
+    /// - topics - `["c_exit_single", from: Address]`
+    /// - data - `[token_index: u32, burn_amount: i128, token_amount_out: i128]`
+    pub fn c_exit_single(
+        e: &Env,
+        from: Address,
+        token_index: u32,
+        burn_amount: i128,
+        token_amount_out: i128,
+    ) {
+        let topics = (Symbol::new(e, "c_exit_single"), from);
+        e.events()
+            .publish(topics, (token_index, burn_amount, token_amount_out));
+    }
+
+    /// Emitted when a backstop bootstrap is claimed
+    ///
+    /// - topics - `["bb_claim_bootstrap", from: Address]`
+    /// - data - `[bootstrap_id: u32, backstop: Address, claimed: i128]`
+    pub fn bb_claim_bootstrap(
+        e: &Env,
+        from: Address,
+        bootstrap_id: u32,
+        backstop: Address,
+        claimed: i128,
+    ) {
+        let topics = (Symbol::new(e, "bb_claim_bootstrap"), from);
+        e.events()
+            .publish(topics, (bootstrap_id, backstop, claimed));
+    }
+
+    /// Emitted when a backstop bootstrap is refunded
+    ///
+    /// - topics - `["bb_refund_bootstrap", from: Address]`
+    /// - data - `[bootstrap_id: u32, refunded: i128]`
+    pub fn bb_refund_bootstrap(e: &Env, from: Address, bootstrap_id: u32, refunded: i128) {
+        let topics = (Symbol::new(e, "bb_refund_bootstrap"), from);
+        e.events().publish(topics, (bootstrap_id, refunded));
+    }
+
+    /// Emitted when a backstop bootstrap is started
+    ///
+    /// - topics - `["bb_start_bootstrap", from: Address]`
+    /// - data - `[bootstrap_id: u32, bootstrap_token_index: u32, bootstrap_amount: i128, pool: Address]`
+    pub fn bb_start_bootstrap(
+        e: &Env,
+        from: Address,
+        bootstrap_id: u32,
+        bootstrap_token_index: u32,
+        bootstrap_amount: i128,
+        pool: Address,
+    ) {
+        let topics = (Symbol::new(e, "bb_start_bootstrap"), from);
+        e.events().publish(
+            topics,
+            (bootstrap_id, bootstrap_token_index, bootstrap_amount, pool),
+        );
+    }
+}
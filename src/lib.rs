@@ -1,9 +1,14 @@
 #![no_std]
 
+mod actions;
 pub mod contract;
 mod dependencies;
 mod errors;
+mod events;
+mod limits;
+mod permissions;
 mod storage;
+mod validation;
 
 #[cfg(test)]
 extern crate std;
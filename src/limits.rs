@@ -0,0 +1,74 @@
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::{
+    errors::BackstopManagerErrors,
+    storage::{self, Limit, LimitKey, LimitUsage},
+};
+
+/// Get the usage of a limit for the current period
+fn current_usage(e: &Env, key: &LimitKey, limit: &Limit) -> LimitUsage {
+    let sequence = e.ledger().sequence();
+    match storage::get_limit_usage(e).get(key.clone()) {
+        Some(usage) if sequence < usage.period_start.saturating_add(limit.period) => usage,
+        _ => LimitUsage {
+            period_start: sequence,
+            amount: 0,
+        },
+    }
+}
+
+/// Get the amount managers can still use for a limit during the current period
+///
+/// Returns None if no limit is set
+///
+/// ### Arguments
+/// * `key` - The limit
+pub fn remaining(e: &Env, key: &LimitKey) -> Option<i128> {
+    let limit = storage::get_limits(e).get(key.clone())?;
+    let usage = current_usage(e, key, &limit);
+    Some((limit.max_amount - usage.amount).max(0))
+}
+
+/// Use `amount` of a limit for the current period. The owner is not limited.
+///
+/// ### Arguments
+/// * `from` - The caller of the function
+/// * `key` - The limit
+/// * `amount` - The amount being used
+///
+/// ### Errors
+/// * NegativeAmountError - The amount is negative
+/// * WithdrawLimitExceeded - The withdraw limit would be exceeded
+/// * ExitLimitExceeded - The exit limit would be exceeded
+/// * RefundLimitExceeded - The refund limit for the token would be exceeded
+pub fn spend(e: &Env, from: &Address, key: LimitKey, amount: i128) {
+    if *from == storage::get_owner(e) {
+        return;
+    }
+    if amount < 0 {
+        panic_with_error!(e, BackstopManagerErrors::NegativeAmountError);
+    }
+    let limit = match storage::get_limits(e).get(key.clone()) {
+        Some(limit) => limit,
+        None => return,
+    };
+    let mut usage = current_usage(e, &key, &limit);
+    usage.amount = match usage.amount.checked_add(amount) {
+        Some(amount) => amount,
+        None => panic_with_error!(e, BackstopManagerErrors::OverflowError),
+    };
+    if usage.amount > limit.max_amount {
+        match key {
+            LimitKey::Withdraw => {
+                panic_with_error!(e, BackstopManagerErrors::WithdrawLimitExceeded)
+            }
+            LimitKey::Exit => panic_with_error!(e, BackstopManagerErrors::ExitLimitExceeded),
+            LimitKey::Refund(_) => {
+                panic_with_error!(e, BackstopManagerErrors::RefundLimitExceeded)
+            }
+        }
+    }
+    let mut limit_usage = storage::get_limit_usage(e);
+    limit_usage.set(key, usage);
+    storage::set_limit_usage(e, &limit_usage);
+}
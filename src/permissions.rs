@@ -0,0 +1,44 @@
+/********** Permissions **********/
+
+// Each manager entrypoint is gated by the permission bit of the action it takes, or by
+// the bits of every action it combines. Managers are granted a bitmask of the permissions
+// they can act with.
+
+pub const B_CLAIM: u32 = 1 << 0;
+pub const B_DEPOSIT: u32 = 1 << 1;
+pub const B_QUEUE: u32 = 1 << 2;
+pub const B_DEQUEUE: u32 = 1 << 3;
+pub const B_WITHDRAW: u32 = 1 << 4;
+pub const C_JOIN: u32 = 1 << 5;
+pub const C_EXIT: u32 = 1 << 6;
+pub const BB_START: u32 = 1 << 7;
+pub const BB_CLAIM: u32 = 1 << 8;
+pub const BB_REFUND: u32 = 1 << 9;
+pub const REFUND_TOKEN: u32 = 1 << 10;
+
+/// All valid permission bits
+pub const ALL: u32 = (1 << 11) - 1;
+
+/********** Scope Presets **********/
+
+/// Low scope - claim emissions and bootstraps, and refund tokens to the owner
+pub const SCOPE_LOW: u32 = B_CLAIM | BB_CLAIM | BB_REFUND | REFUND_TOKEN;
+/// Medium scope - Low scope and move funds into and out of the withdrawal queue
+pub const SCOPE_MEDIUM: u32 = SCOPE_LOW | B_DEPOSIT | B_QUEUE | B_DEQUEUE;
+/// High scope - all permissions
+pub const SCOPE_HIGH: u32 = ALL;
+
+/// Expand a scope into its preset permissions
+///
+/// Returns None if the scope is not valid
+///
+/// ### Arguments
+/// * `scope` - The scope. 0 = Low, 1 = Medium, 2 = High
+pub fn from_scope(scope: u32) -> Option<u32> {
+    match scope {
+        0 => Some(SCOPE_LOW),
+        1 => Some(SCOPE_MEDIUM),
+        2 => Some(SCOPE_HIGH),
+        _ => None,
+    }
+}
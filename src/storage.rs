@@ -1,7 +1,13 @@
-use soroban_sdk::{contracttype, unwrap::UnwrapOptimized, Address, Env, Symbol, Vec};
+use crate::permissions;
+use soroban_sdk::{
+    contracttype, map, unwrap::UnwrapOptimized, Address, BytesN, Env, Map, Symbol, TryFromVal, Val,
+    Vec,
+};
 
 /********** Storage Types **********/
 
+/// The single manager record used before multiple managers were supported. Only read
+/// to migrate existing deployments.
 #[contracttype]
 pub struct Manager {
     /// The address of the manager
@@ -13,6 +19,233 @@ pub struct Manager {
     pub scope: u32,
 }
 
+#[contracttype]
+pub struct ManagerConfig {
+    /// The bitmask of permissions granted to the manager
+    pub permissions: u32,
+    /// The last ledger the manager can act, or None if the manager does not expire
+    pub expires_at_ledger: Option<u32>,
+}
+
+/// The manager actions that can be limited per period
+#[contracttype]
+#[derive(Clone)]
+pub enum LimitKey {
+    /// Backstop tokens withdrawn from backstops via `b_withdraw`
+    Withdraw,
+    /// Backstop tokens burned to exit the comet pool via `c_exit_pool`
+    Exit,
+    /// Tokens of the given address refunded to the owner via `refund_token`
+    Refund(Address),
+}
+
+#[contracttype]
+pub struct Limit {
+    /// The maximum amount managers can use each period
+    pub max_amount: i128,
+    /// The length of a period in ledgers
+    pub period: u32,
+}
+
+#[contracttype]
+pub struct LimitUsage {
+    /// The ledger the current period started
+    pub period_start: u32,
+    /// The amount used by managers during the current period
+    pub amount: i128,
+}
+
+#[contracttype]
+pub struct PendingOwner {
+    /// The address of the proposed owner
+    pub id: Address,
+    /// The last ledger the proposed owner can accept ownership
+    pub expiration_ledger: u32,
+}
+
+/// A backstop and pool the manager can interact with together
+#[contracttype]
+#[derive(Clone)]
+pub struct Pair {
+    /// The address of the backstop contract
+    pub backstop: Address,
+    /// The address of the pool
+    pub pool: Address,
+}
+
+/// The bounds permissionless keepers compound backstop emissions within
+#[contracttype]
+#[derive(Clone)]
+pub struct KeeperConfig {
+    /// The address of the backstop keepers compound emissions in
+    pub backstop: Address,
+    /// The minimum number of ledgers between compounds of a pool
+    pub interval: u32,
+    /// The minimum backstop tokens minted per BLND claimed, with 7 decimals
+    pub min_lp_out_ratio: i128,
+    /// The share of the backstop tokens minted paid to the keeper from the keeper budget
+    /// funded by the owner, with 7 decimals. 0 = no bounty
+    pub bounty: i128,
+}
+
+/// The minimum amounts managers must receive for single-sided joins and exits of the comet
+/// pool with a token, set by the owner
+#[contracttype]
+#[derive(Clone)]
+pub struct SlippageFloor {
+    /// The minimum backstop tokens minted per token deposited by `c_join_single`, with 7
+    /// decimals
+    pub min_lp_out_ratio: i128,
+    /// The minimum tokens received per backstop token burned by `c_exit_single`, with 7
+    /// decimals
+    pub min_token_out_ratio: i128,
+}
+
+/// An owner configuration change that is delayed by the timelock
+#[contracttype]
+#[derive(Clone)]
+pub enum ConfigChange {
+    /// Add a manager with (manager, permissions, expires_at_ledger)
+    AddManager(Address, u32, Option<u32>),
+    /// Set the permissions of an existing manager with (manager, permissions)
+    SetManagerPermissions(Address, u32),
+    /// Renew an existing manager with (manager, expires_at_ledger)
+    RenewManager(Address, Option<u32>),
+    /// Set the backstop bootstrapper contract
+    SetBackstopBootstrapper(Address),
+    /// Add a valid backstop
+    AddBackstop(Address),
+    /// Add a valid pool
+    AddPool(Address),
+    /// Add a backstop and pool pair
+    AddPair(Pair),
+    /// Set the timelock in ledgers
+    SetTimelock(u32),
+    /// Enable keepers, or update their bounds
+    SetKeeper(KeeperConfig),
+    /// Upgrade the contract to the WASM with the hash
+    Upgrade(BytesN<32>),
+    /// Set the guardian, or None to remove the guardian
+    SetGuardian(Option<Address>),
+    /// Set a manager limit with (key, max_amount, period)
+    SetLimit(LimitKey, i128, u32),
+    /// Remove a manager limit
+    RemoveLimit(LimitKey),
+    /// Set the slippage floor of single-sided joins and exits with a token
+    SetSlippageFloor(Address, SlippageFloor),
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct PendingChange {
+    /// The queued configuration change
+    pub change: ConfigChange,
+    /// The first ledger the change can be executed
+    pub execute_after: u32,
+}
+
+/// The BLND emissions claimed for a backstop and pool pair, and the backstop tokens minted
+/// with them
+#[contracttype]
+#[derive(Clone)]
+pub struct ClaimedTotal {
+    /// The backstop tokens minted during the current reporting period
+    pub amount: i128,
+    /// The BLND claimed during the current reporting period
+    pub blnd: i128,
+    /// The ledger the current reporting period started, or 0 if no snapshot has been taken
+    pub period_start: u32,
+    /// The backstop tokens minted during the previous reporting period
+    pub last_amount: i128,
+    /// The BLND claimed during the previous reporting period
+    pub last_blnd: i128,
+    /// The backstop tokens minted since the first claim
+    pub total_amount: i128,
+    /// The BLND claimed since the first claim
+    pub total_blnd: i128,
+}
+
+/// The outcome of a bootstrap started by the contract
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BootstrapOutcome {
+    /// The bootstrap has not been claimed or refunded by the contract
+    Open,
+    /// The bootstrap proceeds were claimed via `bb_claim_bootstrap`
+    Claimed,
+    /// The bootstrap was refunded via `bb_refund_bootstrap`
+    Refunded,
+}
+
+/// A bootstrap started by the contract via `bb_start_bootstrap`
+#[contracttype]
+#[derive(Clone)]
+pub struct BootstrapRecord {
+    /// The id of the bootstrap
+    pub id: u32,
+    /// The address of the pool being bootstrapped
+    pub pool: Address,
+    /// The index of the comet underlying token being bootstrapped
+    pub token_index: u32,
+    /// The amount of the bootstrap token bootstrapped
+    pub amount: i128,
+    /// The minimum amount of the pair token to bootstrap
+    pub pair_min: i128,
+    /// The ledger the bootstrap closes
+    pub close_ledger: u32,
+    /// The outcome of the bootstrap for the contract
+    pub outcome: BootstrapOutcome,
+}
+
+/// A plan to move backstop shares from one pool to another once the shares queued for
+/// withdrawal from the source pool unlock
+#[contracttype]
+#[derive(Clone)]
+pub struct RebalancePlan {
+    /// The id of the plan
+    pub id: u32,
+    /// The address of the backstop contract
+    pub backstop: Address,
+    /// The address of the pool the shares are withdrawn from
+    pub source_pool: Address,
+    /// The address of the pool the withdrawn backstop tokens are deposited into
+    pub destination_pool: Address,
+    /// The amount of shares queued for withdrawal from the source pool
+    pub shares: i128,
+    /// The timestamp the queued shares unlock
+    pub exp: u64,
+}
+
+/// A manager action recorded in the action history
+#[contracttype]
+#[derive(Clone)]
+pub struct ActionRecord {
+    /// The name of the function that was called
+    pub action: Symbol,
+    /// The caller of the function
+    pub caller: Address,
+    /// The ledger the action was taken
+    pub ledger: u32,
+    /// The key amounts of the action
+    /// * refund_token - `[amount]`
+    /// * b_claim, b_compound - `[claimed]`
+    /// * b_deposit - `[amount, shares]`
+    /// * b_queue_withdrawal, b_dequeue_withdrawal - `[amount]`
+    /// * b_withdraw, b_withdraw_unlocked - `[amount, tokens_out]`
+    /// * keeper_compound - `[claimed, bounty]`
+    /// * b_migrate - `[amount, tokens, shares]`
+    /// * b_rebalance - `[plan_id, shares]`
+    /// * cancel_rebalance - `[plan_id, shares]`
+    /// * c_join_pool - `[pool_amount_out]`
+    /// * c_join_single - `[token_amount_in, pool_amount_out]`
+    /// * c_exit_pool - `[burn_amount]`
+    /// * c_exit_single - `[burn_amount, token_amount_out]`
+    /// * bb_claim_bootstrap - `[bootstrap_id, claimed]`
+    /// * bb_refund_bootstrap - `[bootstrap_id, refunded]`
+    /// * bb_start_bootstrap - `[bootstrap_id, bootstrap_amount]`
+    pub amounts: Vec<i128>,
+}
+
 /********** Ledger Thresholds **********/
 
 const ONE_DAY_LEDGERS: u32 = 17280; // assumes 5 seconds per ledger
@@ -20,14 +253,71 @@ const ONE_DAY_LEDGERS: u32 = 17280; // assumes 5 seconds per ledger
 const LEDGER_BUMP: u32 = 120 * ONE_DAY_LEDGERS;
 const LEDGER_THRESHOLD: u32 = LEDGER_BUMP - 20 * ONE_DAY_LEDGERS;
 
+const LEDGER_BUMP_ENTRY: u32 = 120 * ONE_DAY_LEDGERS;
+const LEDGER_THRESHOLD_ENTRY: u32 = LEDGER_BUMP_ENTRY - 20 * ONE_DAY_LEDGERS;
+
 /********** Ledger Keys **********/
 
 const OWNER_KEY: &str = "Owner";
+const PENDING_OWNER_KEY: &str = "PendOwner";
+const GUARDIAN_KEY: &str = "Guardian";
+const PAUSED_KEY: &str = "Paused";
 const MANAGER_KEY: &str = "Manager";
+const MANAGERS_KEY: &str = "Managers";
 const BACKSTOPS_KEY: &str = "Bstop";
 const POOLS_KEY: &str = "Pools";
+const PAIRS_KEY: &str = "Pairs";
+const BACKSTOP_LIST: ListKeys = ListKeys {
+    len: "BstopLen",
+    index: "BstopIdx",
+    entry: "BstopAt",
+};
+const POOL_LIST: ListKeys = ListKeys {
+    len: "PoolLen",
+    index: "PoolIdx",
+    entry: "PoolAt",
+};
+const PAIR_LIST: ListKeys = ListKeys {
+    len: "PairLen",
+    index: "PairIdx",
+    entry: "PairAt",
+};
+const PAIRED_POOLS_KEY: &str = "PairPools";
+const PAIRED_BACKSTOPS_KEY: &str = "PairBstops";
 const BACKSTOP_BOOTSTRAPPER_KEY: &str = "BstopBoot";
 const BACKSTOP_TOKEN_KEY: &str = "BstopTkn";
+const LIMITS_KEY: &str = "Limits";
+const LIMIT_USAGE_KEY: &str = "LimitUse";
+const SLIPPAGE_KEY: &str = "Slippage";
+const TIMELOCK_KEY: &str = "Timelock";
+const PENDING_CHANGES_KEY: &str = "Pending";
+const PENDING_CHANGE_ID_KEY: &str = "PendingId";
+const SCHEMA_VERSION_KEY: &str = "Version";
+const CLAIMED_KEY: &str = "Claimed";
+const KEEPER_KEY: &str = "Keeper";
+const KEEPER_LAST_KEY: &str = "KeepLast";
+const KEEPER_BUDGET_KEY: &str = "KeepFund";
+const BOOTSTRAP_KEY: &str = "Boot";
+const BOOTSTRAP_LIST: ListKeys = ListKeys {
+    len: "BootLen",
+    index: "BootIdx",
+    entry: "BootAt",
+};
+const REBALANCE_KEY: &str = "Rebal";
+const REBALANCE_ID_KEY: &str = "RebalId";
+const REBALANCE_RESERVED_KEY: &str = "RebalRes";
+const REBALANCE_LIST: ListKeys = ListKeys {
+    len: "RebalLen",
+    index: "RebalIdx",
+    entry: "RebalAt",
+};
+const HISTORY_KEY: &str = "Hist";
+const HISTORY_START_KEY: &str = "HistStart";
+const HISTORY_END_KEY: &str = "HistEnd";
+const HISTORY_RETENTION_KEY: &str = "HistMax";
+
+/// The number of actions kept in the history if the owner has not configured it
+pub const DEFAULT_HISTORY_RETENTION: u32 = 50;
 
 /********** Ledger Thresholds **********/
 
@@ -52,22 +342,204 @@ pub fn get_owner(e: &Env) -> Address {
 pub fn set_owner(e: &Env, owner: &Address) {
     e.storage()
         .instance()
-        .set::<Symbol, Address>(&Symbol::new(e, OWNER_KEY), &owner);
+        .set::<Symbol, Address>(&Symbol::new(e, OWNER_KEY), owner);
 }
 
-/// Get the manager for the contract
-pub fn get_manager(e: &Env) -> Manager {
+/// Get the pending owner, if an ownership transfer has been proposed
+pub fn get_pending_owner(e: &Env) -> Option<PendingOwner> {
     e.storage()
         .instance()
-        .get::<Symbol, Manager>(&Symbol::new(e, MANAGER_KEY))
-        .unwrap_optimized()
+        .get::<Symbol, PendingOwner>(&Symbol::new(e, PENDING_OWNER_KEY))
+}
+
+/// Set the pending owner
+pub fn set_pending_owner(e: &Env, pending_owner: &PendingOwner) {
+    e.storage()
+        .instance()
+        .set::<Symbol, PendingOwner>(&Symbol::new(e, PENDING_OWNER_KEY), pending_owner);
+}
+
+/// Remove the pending owner
+pub fn del_pending_owner(e: &Env) {
+    e.storage()
+        .instance()
+        .remove::<Symbol>(&Symbol::new(e, PENDING_OWNER_KEY));
+}
+
+/// Get the guardian address, if one is set
+pub fn get_guardian(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, GUARDIAN_KEY))
+}
+
+/// Set the guardian address
+pub fn set_guardian(e: &Env, guardian: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, GUARDIAN_KEY), guardian);
+}
+
+/// Remove the guardian address
+pub fn del_guardian(e: &Env) {
+    e.storage()
+        .instance()
+        .remove::<Symbol>(&Symbol::new(e, GUARDIAN_KEY));
+}
+
+/// Check if manager actions are paused
+pub fn get_paused(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get::<Symbol, bool>(&Symbol::new(e, PAUSED_KEY))
+        .unwrap_or(false)
+}
+
+/// Set if manager actions are paused
+pub fn set_paused(e: &Env, paused: bool) {
+    e.storage()
+        .instance()
+        .set::<Symbol, bool>(&Symbol::new(e, PAUSED_KEY), &paused);
+}
+
+/// Get the managers for the contract, mapped to their config
+///
+/// Falls back to the legacy single manager record, expanding its scope into the
+/// preset permissions, if the contract has not written the managers map yet.
+pub fn get_managers(e: &Env) -> Map<Address, ManagerConfig> {
+    let storage = e.storage().instance();
+    match storage.get::<Symbol, Map<Address, ManagerConfig>>(&Symbol::new(e, MANAGERS_KEY)) {
+        Some(managers) => managers,
+        None => match storage.get::<Symbol, Manager>(&Symbol::new(e, MANAGER_KEY)) {
+            Some(manager) => map![
+                e,
+                (
+                    manager.id,
+                    ManagerConfig {
+                        permissions: permissions::from_scope(manager.scope).unwrap_optimized(),
+                        expires_at_ledger: None,
+                    }
+                )
+            ],
+            None => Map::new(e),
+        },
+    }
+}
+
+/// Set the managers for the contract. Removes the legacy single manager record, if any.
+pub fn set_managers(e: &Env, managers: &Map<Address, ManagerConfig>) {
+    let storage = e.storage().instance();
+    storage.set::<Symbol, Map<Address, ManagerConfig>>(&Symbol::new(e, MANAGERS_KEY), managers);
+    storage.remove::<Symbol>(&Symbol::new(e, MANAGER_KEY));
+}
+
+/// Get the limits placed on managers
+pub fn get_limits(e: &Env) -> Map<LimitKey, Limit> {
+    e.storage()
+        .instance()
+        .get::<Symbol, Map<LimitKey, Limit>>(&Symbol::new(e, LIMITS_KEY))
+        .unwrap_or(Map::new(e))
+}
+
+/// Set the limits placed on managers
+pub fn set_limits(e: &Env, limits: &Map<LimitKey, Limit>) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Map<LimitKey, Limit>>(&Symbol::new(e, LIMITS_KEY), limits);
 }
 
-/// Set the manager for the contract
-pub fn set_manager(e: &Env, manager: &Manager) {
+/// Get the usage of each limit placed on managers
+pub fn get_limit_usage(e: &Env) -> Map<LimitKey, LimitUsage> {
     e.storage()
         .instance()
-        .set::<Symbol, Manager>(&Symbol::new(e, MANAGER_KEY), manager);
+        .get::<Symbol, Map<LimitKey, LimitUsage>>(&Symbol::new(e, LIMIT_USAGE_KEY))
+        .unwrap_or(Map::new(e))
+}
+
+/// Set the usage of each limit placed on managers
+pub fn set_limit_usage(e: &Env, usage: &Map<LimitKey, LimitUsage>) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Map<LimitKey, LimitUsage>>(&Symbol::new(e, LIMIT_USAGE_KEY), usage);
+}
+
+/// Get the slippage floors of single-sided joins and exits, by token
+pub fn get_slippage_floors(e: &Env) -> Map<Address, SlippageFloor> {
+    e.storage()
+        .instance()
+        .get::<Symbol, Map<Address, SlippageFloor>>(&Symbol::new(e, SLIPPAGE_KEY))
+        .unwrap_or(Map::new(e))
+}
+
+/// Set the slippage floors of single-sided joins and exits, by token
+pub fn set_slippage_floors(e: &Env, floors: &Map<Address, SlippageFloor>) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Map<Address, SlippageFloor>>(&Symbol::new(e, SLIPPAGE_KEY), floors);
+}
+
+/// Get the version of the instance storage layout. Contracts deployed before the
+/// version was tracked are version 0.
+pub fn get_schema_version(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get::<Symbol, u32>(&Symbol::new(e, SCHEMA_VERSION_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the version of the instance storage layout
+pub fn set_schema_version(e: &Env, version: u32) {
+    e.storage()
+        .instance()
+        .set::<Symbol, u32>(&Symbol::new(e, SCHEMA_VERSION_KEY), &version);
+}
+
+/// Get the number of ledgers owner configuration changes are delayed
+pub fn get_timelock(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get::<Symbol, u32>(&Symbol::new(e, TIMELOCK_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the number of ledgers owner configuration changes are delayed
+pub fn set_timelock(e: &Env, timelock: u32) {
+    e.storage()
+        .instance()
+        .set::<Symbol, u32>(&Symbol::new(e, TIMELOCK_KEY), &timelock);
+}
+
+/// Get the queued owner configuration changes by id
+pub fn get_pending_changes(e: &Env) -> Map<u32, PendingChange> {
+    e.storage()
+        .instance()
+        .get::<Symbol, Map<u32, PendingChange>>(&Symbol::new(e, PENDING_CHANGES_KEY))
+        .unwrap_or(Map::new(e))
+}
+
+/// Set the queued owner configuration changes by id
+pub fn set_pending_changes(e: &Env, pending_changes: &Map<u32, PendingChange>) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Map<u32, PendingChange>>(
+            &Symbol::new(e, PENDING_CHANGES_KEY),
+            pending_changes,
+        );
+}
+
+/// Get the id to assign to the next queued configuration change
+pub fn get_next_pending_change_id(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get::<Symbol, u32>(&Symbol::new(e, PENDING_CHANGE_ID_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the id to assign to the next queued configuration change
+pub fn set_next_pending_change_id(e: &Env, id: u32) {
+    e.storage()
+        .instance()
+        .set::<Symbol, u32>(&Symbol::new(e, PENDING_CHANGE_ID_KEY), &id);
 }
 
 /// Get the backstop bootstrapper address
@@ -100,36 +572,767 @@ pub fn set_backstop_token(e: &Env, bootstrapper: Address) {
         .set::<Symbol, Address>(&Symbol::new(e, BACKSTOP_TOKEN_KEY), &bootstrapper);
 }
 
-/// Get an array of all valid backstops the vault can interact with
-pub fn get_valid_backstops(e: &Env) -> Vec<Address> {
-    let key = Symbol::new(e, BACKSTOPS_KEY);
+/// Check if a backstop is valid, and extend its TTL if so
+///
+/// Falls back to the valid backstops stored in instance storage by earlier versions of the
+/// contract, if they have not been moved into persistent storage yet.
+pub fn is_valid_backstop(e: &Env, backstop: &Address) -> bool {
+    match get_legacy_addresses(e, BACKSTOPS_KEY) {
+        Some(backstops) => backstops.contains(backstop),
+        None => list_contains(e, &BACKSTOP_LIST, backstop),
+    }
+}
+
+/// Add a valid backstop. Returns false if the backstop was already valid.
+pub fn add_valid_backstop(e: &Env, backstop: &Address) -> bool {
+    move_legacy_allowlists(e);
+    list_add(e, &BACKSTOP_LIST, backstop)
+}
+
+/// Remove a valid backstop. Returns false if the backstop was not valid.
+pub fn remove_valid_backstop(e: &Env, backstop: &Address) -> bool {
+    move_legacy_allowlists(e);
+    list_remove(e, &BACKSTOP_LIST, backstop)
+}
+
+/// Get a page of the valid backstops the vault can interact with
+pub fn get_valid_backstops(e: &Env, start: u32, limit: u32) -> Vec<Address> {
+    match get_legacy_addresses(e, BACKSTOPS_KEY) {
+        Some(backstops) => vec_page(&backstops, start, limit),
+        None => list_page(e, &BACKSTOP_LIST, start, limit),
+    }
+}
+
+/// Check if a pool is valid, and extend its TTL if so
+///
+/// Falls back to the valid pools stored in instance storage by earlier versions of the
+/// contract, if they have not been moved into persistent storage yet.
+pub fn is_valid_pool(e: &Env, pool: &Address) -> bool {
+    match get_legacy_addresses(e, POOLS_KEY) {
+        Some(pools) => pools.contains(pool),
+        None => list_contains(e, &POOL_LIST, pool),
+    }
+}
+
+/// Add a valid pool. Returns false if the pool was already valid.
+pub fn add_valid_pool(e: &Env, pool: &Address) -> bool {
+    move_legacy_allowlists(e);
+    list_add(e, &POOL_LIST, pool)
+}
+
+/// Remove a valid pool. Returns false if the pool was not valid.
+pub fn remove_valid_pool(e: &Env, pool: &Address) -> bool {
+    move_legacy_allowlists(e);
+    list_remove(e, &POOL_LIST, pool)
+}
+
+/// Get a page of the valid pools the vault can interact with
+pub fn get_valid_pools(e: &Env, start: u32, limit: u32) -> Vec<Address> {
+    match get_legacy_addresses(e, POOLS_KEY) {
+        Some(pools) => vec_page(&pools, start, limit),
+        None => list_page(e, &POOL_LIST, start, limit),
+    }
+}
+
+/// Check if a backstop and pool are paired, and extend the TTL of the pair and its index
+/// entries if so
+///
+/// Falls back to the pairs stored in instance storage by earlier versions of the contract,
+/// if they have not been moved into persistent storage yet.
+pub fn is_pair(e: &Env, pair: &Pair) -> bool {
+    match get_legacy_pairs(e) {
+        Some(pairs) => pairs.contains(pair),
+        None => {
+            if !list_contains(e, &PAIR_LIST, pair) {
+                return false;
+            }
+            extend_paired(e, pair);
+            true
+        }
+    }
+}
+
+/// Add a backstop and pool pair. Returns false if the pair already existed.
+pub fn add_pair(e: &Env, pair: &Pair) -> bool {
+    move_legacy_allowlists(e);
+    pair_add(e, pair)
+}
+
+/// Remove a backstop and pool pair. Returns false if the pair did not exist.
+pub fn remove_pair(e: &Env, pair: &Pair) -> bool {
+    move_legacy_allowlists(e);
+    pair_remove(e, pair)
+}
+
+/// Remove every pair that includes the address as its backstop or pool, and return them
+///
+/// Only the pairs of the address are loaded, through the pools paired with it as a backstop
+/// and the backstops paired with it as a pool.
+pub fn remove_pairs_with(e: &Env, address: &Address) -> Vec<Pair> {
+    move_legacy_allowlists(e);
+    let mut removed = Vec::new(e);
+    for pool in get_paired(e, PAIRED_POOLS_KEY, address).iter() {
+        removed.push_back(Pair {
+            backstop: address.clone(),
+            pool,
+        });
+    }
+    for backstop in get_paired(e, PAIRED_BACKSTOPS_KEY, address).iter() {
+        removed.push_back(Pair {
+            backstop,
+            pool: address.clone(),
+        });
+    }
+    for pair in removed.iter() {
+        pair_remove(e, &pair);
+    }
+    removed
+}
+
+/// Get a page of the backstop and pool pairs the vault can interact with
+pub fn get_pairs(e: &Env, start: u32, limit: u32) -> Vec<Pair> {
+    match get_legacy_pairs(e) {
+        Some(pairs) => vec_page(&pairs, start, limit),
+        None => {
+            let pairs = list_page(e, &PAIR_LIST, start, limit);
+            for pair in pairs.iter() {
+                extend_paired(e, &pair);
+            }
+            pairs
+        }
+    }
+}
+
+/// Move the valid backstops, pools and pairs stored in instance storage by earlier versions
+/// of the contract into persistent storage. Does nothing if they were already moved.
+pub fn move_legacy_allowlists(e: &Env) {
+    let pairs = match get_legacy_pairs(e) {
+        Some(pairs) => pairs,
+        None => return,
+    };
+    let backstops = get_legacy_addresses(e, BACKSTOPS_KEY).unwrap_or(Vec::new(e));
+    let pools = get_legacy_addresses(e, POOLS_KEY).unwrap_or(Vec::new(e));
+    for backstop in backstops.iter() {
+        list_add(e, &BACKSTOP_LIST, &backstop);
+    }
+    for pool in pools.iter() {
+        list_add(e, &POOL_LIST, &pool);
+    }
+    for pair in pairs.iter() {
+        pair_add(e, &pair);
+    }
+    let storage = e.storage().instance();
+    storage.remove::<Symbol>(&Symbol::new(e, BACKSTOPS_KEY));
+    storage.remove::<Symbol>(&Symbol::new(e, POOLS_KEY));
+    storage.remove::<Symbol>(&Symbol::new(e, PAIRS_KEY));
+}
+
+fn pair_add(e: &Env, pair: &Pair) -> bool {
+    if !list_add(e, &PAIR_LIST, pair) {
+        return false;
+    }
+    let mut pools = get_paired(e, PAIRED_POOLS_KEY, &pair.backstop);
+    pools.push_back(pair.pool.clone());
+    set_paired(e, PAIRED_POOLS_KEY, &pair.backstop, &pools);
+    let mut backstops = get_paired(e, PAIRED_BACKSTOPS_KEY, &pair.pool);
+    backstops.push_back(pair.backstop.clone());
+    set_paired(e, PAIRED_BACKSTOPS_KEY, &pair.pool, &backstops);
+    true
+}
+
+fn pair_remove(e: &Env, pair: &Pair) -> bool {
+    if !list_remove(e, &PAIR_LIST, pair) {
+        return false;
+    }
+    let mut pools = get_paired(e, PAIRED_POOLS_KEY, &pair.backstop);
+    if let Some(index) = pools.first_index_of(&pair.pool) {
+        pools.remove(index);
+    }
+    set_paired(e, PAIRED_POOLS_KEY, &pair.backstop, &pools);
+    let mut backstops = get_paired(e, PAIRED_BACKSTOPS_KEY, &pair.pool);
+    if let Some(index) = backstops.first_index_of(&pair.backstop) {
+        backstops.remove(index);
+    }
+    set_paired(e, PAIRED_BACKSTOPS_KEY, &pair.pool, &backstops);
+    true
+}
+
+/// Extend the TTL of the index entries of a pair, so it can always be removed with the
+/// backstop or pool
+fn extend_paired(e: &Env, pair: &Pair) {
+    let storage = e.storage().persistent();
+    for key in [
+        (Symbol::new(e, PAIRED_POOLS_KEY), pair.backstop.clone()),
+        (Symbol::new(e, PAIRED_BACKSTOPS_KEY), pair.pool.clone()),
+    ] {
+        storage.extend_ttl(&key, LEDGER_THRESHOLD_ENTRY, LEDGER_BUMP_ENTRY);
+    }
+}
+
+/// Get the addresses paired with an address, indexed under the key prefix
+fn get_paired(e: &Env, key: &str, address: &Address) -> Vec<Address> {
+    e.storage()
+        .persistent()
+        .get::<(Symbol, Address), Vec<Address>>(&(Symbol::new(e, key), address.clone()))
+        .unwrap_or(Vec::new(e))
+}
+
+fn set_paired(e: &Env, key: &str, address: &Address, paired: &Vec<Address>) {
+    let key = (Symbol::new(e, key), address.clone());
+    if paired.is_empty() {
+        e.storage().persistent().remove::<(Symbol, Address)>(&key);
+    } else {
+        e.storage()
+            .persistent()
+            .set::<(Symbol, Address), Vec<Address>>(&key, paired);
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_ENTRY, LEDGER_BUMP_ENTRY);
+    }
+}
+
+/// Get the addresses stored under a legacy instance allowlist key, if any
+fn get_legacy_addresses(e: &Env, key: &str) -> Option<Vec<Address>> {
     e.storage()
         .instance()
-        .get::<Symbol, Vec<Address>>(&key)
-        .unwrap_optimized()
+        .get::<Symbol, Vec<Address>>(&Symbol::new(e, key))
+}
+
+/// Get the pairs stored in instance storage by earlier versions of the contract, if the
+/// allowlists have not been moved into persistent storage yet
+///
+/// If the pairs were never written, every backstop is paired with every pool.
+fn get_legacy_pairs(e: &Env) -> Option<Vec<Pair>> {
+    if let Some(pairs) = e
+        .storage()
+        .instance()
+        .get::<Symbol, Vec<Pair>>(&Symbol::new(e, PAIRS_KEY))
+    {
+        return Some(pairs);
+    }
+    let backstops = get_legacy_addresses(e, BACKSTOPS_KEY);
+    let pools = get_legacy_addresses(e, POOLS_KEY);
+    if backstops.is_none() && pools.is_none() {
+        return None;
+    }
+    let backstops = backstops.unwrap_or(Vec::new(e));
+    let pools = pools.unwrap_or(Vec::new(e));
+    let mut pairs = Vec::new(e);
+    for backstop in backstops.iter() {
+        for pool in pools.iter() {
+            pairs.push_back(Pair {
+                backstop: backstop.clone(),
+                pool,
+            });
+        }
+    }
+    Some(pairs)
+}
+
+fn vec_page<T>(items: &Vec<T>, start: u32, limit: u32) -> Vec<T>
+where
+    T: TryFromVal<Env, Val>,
+    Val: TryFromVal<Env, T>,
+{
+    let end = start.saturating_add(limit).min(items.len());
+    items.slice(start.min(end)..end)
 }
 
-/// Set the valid backstop addresses
-pub fn set_valid_backstops(e: &Env, backstop: &Vec<Address>) {
-    let key = Symbol::new(e, BACKSTOPS_KEY);
+/********** Claimed Totals **********/
+
+/// Get the claimed emission totals of a backstop and pool pair
+pub fn get_claimed_total(e: &Env, pair: &Pair) -> ClaimedTotal {
+    e.storage()
+        .persistent()
+        .get::<(Symbol, Pair), ClaimedTotal>(&(Symbol::new(e, CLAIMED_KEY), pair.clone()))
+        .unwrap_or(ClaimedTotal {
+            amount: 0,
+            blnd: 0,
+            period_start: 0,
+            last_amount: 0,
+            last_blnd: 0,
+            total_amount: 0,
+            total_blnd: 0,
+        })
+}
+
+/// Set the claimed emission totals of a backstop and pool pair
+pub fn set_claimed_total(e: &Env, pair: &Pair, total: &ClaimedTotal) {
+    let key = (Symbol::new(e, CLAIMED_KEY), pair.clone());
+    e.storage()
+        .persistent()
+        .set::<(Symbol, Pair), ClaimedTotal>(&key, total);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_ENTRY, LEDGER_BUMP_ENTRY);
+}
+
+/// Add claimed emissions to the claimed totals of a backstop and pool
+///
+/// ### Arguments
+/// * `backstop` - The address of the backstop contract
+/// * `pool_address` - The address of the pool
+/// * `blnd` - The BLND claimed
+/// * `claimed` - The backstop tokens minted with the BLND claimed
+pub fn add_claimed_total(
+    e: &Env,
+    backstop: &Address,
+    pool_address: &Address,
+    blnd: i128,
+    claimed: i128,
+) {
+    let pair = Pair {
+        backstop: backstop.clone(),
+        pool: pool_address.clone(),
+    };
+    let mut total = get_claimed_total(e, &pair);
+    total.amount += claimed;
+    total.blnd += blnd;
+    total.total_amount += claimed;
+    total.total_blnd += blnd;
+    set_claimed_total(e, &pair, &total);
+}
+
+/********** Keeper **********/
+
+/// Get the bounds keepers compound emissions within, or None if keepers are disabled
+pub fn get_keeper(e: &Env) -> Option<KeeperConfig> {
     e.storage()
         .instance()
-        .set::<Symbol, Vec<Address>>(&key, &backstop);
+        .get::<Symbol, KeeperConfig>(&Symbol::new(e, KEEPER_KEY))
 }
 
-/// Get an array of all valid pools the vault can interact with
-pub fn get_valid_pools(e: &Env) -> Vec<Address> {
-    let key = Symbol::new(e, POOLS_KEY);
+/// Set the bounds keepers compound emissions within
+pub fn set_keeper(e: &Env, config: &KeeperConfig) {
     e.storage()
         .instance()
-        .get::<Symbol, Vec<Address>>(&key)
-        .unwrap_optimized()
+        .set::<Symbol, KeeperConfig>(&Symbol::new(e, KEEPER_KEY), config);
 }
 
-/// Set the valid pool addresses
-pub fn set_valid_pools(e: &Env, pools: &Vec<Address>) {
-    let key = Symbol::new(e, POOLS_KEY);
+/// Disable keepers
+pub fn del_keeper(e: &Env) {
     e.storage()
         .instance()
-        .set::<Symbol, Vec<Address>>(&key, &pools);
+        .remove::<Symbol>(&Symbol::new(e, KEEPER_KEY));
+}
+
+/// Get the backstop tokens the owner has funded to pay keeper bounties
+pub fn get_keeper_budget(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get::<Symbol, i128>(&Symbol::new(e, KEEPER_BUDGET_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the backstop tokens the owner has funded to pay keeper bounties
+pub fn set_keeper_budget(e: &Env, budget: i128) {
+    e.storage()
+        .instance()
+        .set::<Symbol, i128>(&Symbol::new(e, KEEPER_BUDGET_KEY), &budget);
+}
+
+/// Get the last ledger a keeper compounded emissions for a pool, if any
+pub fn get_keeper_last_compound(e: &Env, pool: &Address) -> Option<u32> {
+    e.storage()
+        .persistent()
+        .get::<(Symbol, Address), u32>(&(Symbol::new(e, KEEPER_LAST_KEY), pool.clone()))
+}
+
+/// Set the last ledger a keeper compounded emissions for a pool
+pub fn set_keeper_last_compound(e: &Env, pool: &Address, ledger: u32) {
+    let key = (Symbol::new(e, KEEPER_LAST_KEY), pool.clone());
+    e.storage()
+        .persistent()
+        .set::<(Symbol, Address), u32>(&key, &ledger);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_ENTRY, LEDGER_BUMP_ENTRY);
+}
+
+/********** Bootstraps **********/
+
+/// Get a bootstrap started by the contract, if it exists
+pub fn get_bootstrap(e: &Env, id: u32) -> Option<BootstrapRecord> {
+    let key = (Symbol::new(e, BOOTSTRAP_KEY), id);
+    let record = e
+        .storage()
+        .persistent()
+        .get::<(Symbol, u32), BootstrapRecord>(&key);
+    if record.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_ENTRY, LEDGER_BUMP_ENTRY);
+    }
+    record
+}
+
+/// Set a bootstrap started by the contract, adding it to the registry if it is new
+pub fn set_bootstrap(e: &Env, record: &BootstrapRecord) {
+    let key = (Symbol::new(e, BOOTSTRAP_KEY), record.id);
+    e.storage()
+        .persistent()
+        .set::<(Symbol, u32), BootstrapRecord>(&key, record);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_ENTRY, LEDGER_BUMP_ENTRY);
+    list_add(e, &BOOTSTRAP_LIST, &record.id);
+}
+
+/// Get a page of the bootstraps started by the contract, ordered from the oldest
+pub fn get_bootstraps(e: &Env, start: u32, limit: u32) -> Vec<BootstrapRecord> {
+    let mut records = Vec::new(e);
+    for id in list_page::<u32>(e, &BOOTSTRAP_LIST, start, limit).iter() {
+        records.push_back(
+            e.storage()
+                .persistent()
+                .get::<(Symbol, u32), BootstrapRecord>(&(Symbol::new(e, BOOTSTRAP_KEY), id))
+                .unwrap_optimized(),
+        );
+    }
+    records
+}
+
+/// Set the outcome of a bootstrap started by the contract. Bootstraps not started by the
+/// contract are ignored.
+///
+/// ### Arguments
+/// * `id` - The id of the bootstrap
+/// * `outcome` - The outcome of the bootstrap for the contract
+pub fn set_bootstrap_outcome(e: &Env, id: u32, outcome: BootstrapOutcome) {
+    if let Some(mut record) = get_bootstrap(e, id) {
+        record.outcome = outcome;
+        set_bootstrap(e, &record);
+    }
+}
+
+/********** Rebalance Plans **********/
+
+/// Get an open rebalance plan, if it exists
+pub fn get_rebalance(e: &Env, id: u32) -> Option<RebalancePlan> {
+    let key = (Symbol::new(e, REBALANCE_KEY), id);
+    let plan = e
+        .storage()
+        .persistent()
+        .get::<(Symbol, u32), RebalancePlan>(&key);
+    if plan.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_ENTRY, LEDGER_BUMP_ENTRY);
+    }
+    plan
+}
+
+/// Add an open rebalance plan, and reserve its shares in the source pool
+pub fn add_rebalance(e: &Env, plan: &RebalancePlan) {
+    let key = (Symbol::new(e, REBALANCE_KEY), plan.id);
+    e.storage()
+        .persistent()
+        .set::<(Symbol, u32), RebalancePlan>(&key, plan);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_ENTRY, LEDGER_BUMP_ENTRY);
+    list_add(e, &REBALANCE_LIST, &plan.id);
+    update_rebalance_reserved(e, plan, plan.shares);
+}
+
+/// Remove an open rebalance plan once it is executed or cancelled, and release its shares
+pub fn remove_rebalance(e: &Env, plan: &RebalancePlan) {
+    e.storage()
+        .persistent()
+        .remove::<(Symbol, u32)>(&(Symbol::new(e, REBALANCE_KEY), plan.id));
+    list_remove(e, &REBALANCE_LIST, &plan.id);
+    update_rebalance_reserved(e, plan, -plan.shares);
+}
+
+/// Get the queued shares of a backstop and pool pair reserved by open rebalance plans, keyed by
+/// the time the shares unlock
+pub fn get_rebalance_reserved(
+    e: &Env,
+    backstop: &Address,
+    pool_address: &Address,
+) -> Map<u64, i128> {
+    let key = (
+        Symbol::new(e, REBALANCE_RESERVED_KEY),
+        Pair {
+            backstop: backstop.clone(),
+            pool: pool_address.clone(),
+        },
+    );
+    e.storage()
+        .persistent()
+        .get::<(Symbol, Pair), Map<u64, i128>>(&key)
+        .unwrap_or(Map::new(e))
+}
+
+fn update_rebalance_reserved(e: &Env, plan: &RebalancePlan, delta: i128) {
+    let mut reserved = get_rebalance_reserved(e, &plan.backstop, &plan.source_pool);
+    let shares = reserved.get(plan.exp).unwrap_or(0) + delta;
+    if shares == 0 {
+        reserved.remove(plan.exp);
+    } else {
+        reserved.set(plan.exp, shares);
+    }
+    let key = (
+        Symbol::new(e, REBALANCE_RESERVED_KEY),
+        Pair {
+            backstop: plan.backstop.clone(),
+            pool: plan.source_pool.clone(),
+        },
+    );
+    if reserved.is_empty() {
+        e.storage().persistent().remove::<(Symbol, Pair)>(&key);
+    } else {
+        e.storage()
+            .persistent()
+            .set::<(Symbol, Pair), Map<u64, i128>>(&key, &reserved);
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_ENTRY, LEDGER_BUMP_ENTRY);
+    }
+}
+
+/// Get a page of the open rebalance plans
+pub fn get_rebalances(e: &Env, start: u32, limit: u32) -> Vec<RebalancePlan> {
+    let mut plans = Vec::new(e);
+    for id in list_page::<u32>(e, &REBALANCE_LIST, start, limit).iter() {
+        plans.push_back(
+            e.storage()
+                .persistent()
+                .get::<(Symbol, u32), RebalancePlan>(&(Symbol::new(e, REBALANCE_KEY), id))
+                .unwrap_optimized(),
+        );
+    }
+    plans
+}
+
+/// Get the id to assign to the next rebalance plan
+pub fn get_next_rebalance_id(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get::<Symbol, u32>(&Symbol::new(e, REBALANCE_ID_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the id to assign to the next rebalance plan
+pub fn set_next_rebalance_id(e: &Env, id: u32) {
+    e.storage()
+        .instance()
+        .set::<Symbol, u32>(&Symbol::new(e, REBALANCE_ID_KEY), &id);
+}
+
+/********** History **********/
+
+/// Get the number of most recent actions kept in the history
+pub fn get_history_retention(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get::<Symbol, u32>(&Symbol::new(e, HISTORY_RETENTION_KEY))
+        .unwrap_or(DEFAULT_HISTORY_RETENTION)
+}
+
+/// Set the number of most recent actions kept in the history. Any recorded actions
+/// outside of the new retention are removed.
+pub fn set_history_retention(e: &Env, retention: u32) {
+    e.storage()
+        .instance()
+        .set::<Symbol, u32>(&Symbol::new(e, HISTORY_RETENTION_KEY), &retention);
+    let (start, end) = get_history_range(e);
+    trim_history(e, start, end.saturating_sub(retention));
+}
+
+/// Get the range of ids of the recorded actions still kept in the history
+fn get_history_range(e: &Env) -> (u32, u32) {
+    let storage = e.storage().instance();
+    let start = storage
+        .get::<Symbol, u32>(&Symbol::new(e, HISTORY_START_KEY))
+        .unwrap_or(0);
+    let end = storage
+        .get::<Symbol, u32>(&Symbol::new(e, HISTORY_END_KEY))
+        .unwrap_or(0);
+    (start, end)
+}
+
+/// Remove the recorded actions from `start` up to `new_start`
+fn trim_history(e: &Env, start: u32, new_start: u32) {
+    if new_start <= start {
+        return;
+    }
+    for id in start..new_start {
+        e.storage()
+            .persistent()
+            .remove::<(Symbol, u32)>(&(Symbol::new(e, HISTORY_KEY), id));
+    }
+    e.storage()
+        .instance()
+        .set::<Symbol, u32>(&Symbol::new(e, HISTORY_START_KEY), &new_start);
+}
+
+/// Record an action in the history, removing the oldest recorded action if the
+/// history is full. Nothing is recorded if the retention is zero.
+pub fn push_history(e: &Env, record: &ActionRecord) {
+    let retention = get_history_retention(e);
+    if retention == 0 {
+        return;
+    }
+    let (start, end) = get_history_range(e);
+    let key = (Symbol::new(e, HISTORY_KEY), end);
+    let storage = e.storage().persistent();
+    storage.set::<(Symbol, u32), ActionRecord>(&key, record);
+    storage.extend_ttl(&key, LEDGER_THRESHOLD_ENTRY, LEDGER_BUMP_ENTRY);
+    e.storage()
+        .instance()
+        .set::<Symbol, u32>(&Symbol::new(e, HISTORY_END_KEY), &(end + 1));
+    trim_history(e, start, (end + 1).saturating_sub(retention));
+}
+
+/// Record a manager action in the action history
+///
+/// ### Arguments
+/// * `action` - The name of the function that was called
+/// * `caller` - The caller of the function
+/// * `amounts` - The key amounts of the action
+pub fn record_action(e: &Env, action: &str, caller: &Address, amounts: Vec<i128>) {
+    push_history(
+        e,
+        &ActionRecord {
+            action: Symbol::new(e, action),
+            caller: caller.clone(),
+            ledger: e.ledger().sequence(),
+            amounts,
+        },
+    );
+}
+
+/// Get a page of the recorded actions, ordered from the most recent
+pub fn get_history(e: &Env, start: u32, limit: u32) -> Vec<ActionRecord> {
+    let storage = e.storage().persistent();
+    let (first, end) = get_history_range(e);
+    let page_end = start.saturating_add(limit).min(end - first);
+    let mut records = Vec::new(e);
+    for index in start..page_end {
+        records.push_back(
+            storage
+                .get::<(Symbol, u32), ActionRecord>(&(Symbol::new(e, HISTORY_KEY), end - 1 - index))
+                .unwrap_optimized(),
+        );
+    }
+    records
+}
+
+/********** Allowlists **********/
+
+/// The keys of a list stored with one persistent entry per item, so it can grow without
+/// being bound by the instance storage size. The length is kept in instance storage.
+struct ListKeys {
+    /// The instance key of the length of the list
+    len: &'static str,
+    /// The persistent key prefix of an item, mapped to its position in the list
+    index: &'static str,
+    /// The persistent key prefix of a position in the list, mapped to its item
+    entry: &'static str,
+}
+
+fn list_len(e: &Env, keys: &ListKeys) -> u32 {
+    e.storage()
+        .instance()
+        .get::<Symbol, u32>(&Symbol::new(e, keys.len))
+        .unwrap_or(0)
+}
+
+fn list_contains<T>(e: &Env, keys: &ListKeys, item: &T) -> bool
+where
+    T: Clone + TryFromVal<Env, Val>,
+    Val: TryFromVal<Env, T>,
+{
+    let storage = e.storage().persistent();
+    let index_key = (Symbol::new(e, keys.index), item.clone());
+    match storage.get::<(Symbol, T), u32>(&index_key) {
+        Some(index) => {
+            storage.extend_ttl(&index_key, LEDGER_THRESHOLD_ENTRY, LEDGER_BUMP_ENTRY);
+            storage.extend_ttl(
+                &(Symbol::new(e, keys.entry), index),
+                LEDGER_THRESHOLD_ENTRY,
+                LEDGER_BUMP_ENTRY,
+            );
+            true
+        }
+        None => false,
+    }
+}
+
+fn list_add<T>(e: &Env, keys: &ListKeys, item: &T) -> bool
+where
+    T: Clone + TryFromVal<Env, Val>,
+    Val: TryFromVal<Env, T>,
+{
+    let storage = e.storage().persistent();
+    let index_key = (Symbol::new(e, keys.index), item.clone());
+    if storage.has(&index_key) {
+        return false;
+    }
+    let len = list_len(e, keys);
+    let entry_key = (Symbol::new(e, keys.entry), len);
+    storage.set::<(Symbol, T), u32>(&index_key, &len);
+    storage.set::<(Symbol, u32), T>(&entry_key, item);
+    storage.extend_ttl(&index_key, LEDGER_THRESHOLD_ENTRY, LEDGER_BUMP_ENTRY);
+    storage.extend_ttl(&entry_key, LEDGER_THRESHOLD_ENTRY, LEDGER_BUMP_ENTRY);
+    e.storage()
+        .instance()
+        .set::<Symbol, u32>(&Symbol::new(e, keys.len), &(len + 1));
+    true
+}
+
+fn list_remove<T>(e: &Env, keys: &ListKeys, item: &T) -> bool
+where
+    T: Clone + TryFromVal<Env, Val>,
+    Val: TryFromVal<Env, T>,
+{
+    let storage = e.storage().persistent();
+    let index_key = (Symbol::new(e, keys.index), item.clone());
+    let index = match storage.get::<(Symbol, T), u32>(&index_key) {
+        Some(index) => index,
+        None => return false,
+    };
+    // move the last item into the removed position to keep the list contiguous
+    let last_index = list_len(e, keys) - 1;
+    let last_entry_key = (Symbol::new(e, keys.entry), last_index);
+    if index != last_index {
+        let last_item = storage
+            .get::<(Symbol, u32), T>(&last_entry_key)
+            .unwrap_optimized();
+        let moved_entry_key = (Symbol::new(e, keys.entry), index);
+        let moved_index_key = (Symbol::new(e, keys.index), last_item.clone());
+        storage.set::<(Symbol, u32), T>(&moved_entry_key, &last_item);
+        storage.set::<(Symbol, T), u32>(&moved_index_key, &index);
+        storage.extend_ttl(&moved_entry_key, LEDGER_THRESHOLD_ENTRY, LEDGER_BUMP_ENTRY);
+        storage.extend_ttl(&moved_index_key, LEDGER_THRESHOLD_ENTRY, LEDGER_BUMP_ENTRY);
+    }
+    storage.remove(&last_entry_key);
+    storage.remove(&index_key);
+    e.storage()
+        .instance()
+        .set::<Symbol, u32>(&Symbol::new(e, keys.len), &last_index);
+    true
+}
+
+fn list_page<T>(e: &Env, keys: &ListKeys, start: u32, limit: u32) -> Vec<T>
+where
+    T: Clone + TryFromVal<Env, Val>,
+    Val: TryFromVal<Env, T>,
+{
+    let storage = e.storage().persistent();
+    let end = start.saturating_add(limit).min(list_len(e, keys));
+    let mut items = Vec::new(e);
+    for index in start..end {
+        let entry_key = (Symbol::new(e, keys.entry), index);
+        let item = storage
+            .get::<(Symbol, u32), T>(&entry_key)
+            .unwrap_optimized();
+        // keep read entries alive, an archived entry would trap every page that includes it
+        let index_key = (Symbol::new(e, keys.index), item.clone());
+        storage.extend_ttl(&entry_key, LEDGER_THRESHOLD_ENTRY, LEDGER_BUMP_ENTRY);
+        storage.extend_ttl(&index_key, LEDGER_THRESHOLD_ENTRY, LEDGER_BUMP_ENTRY);
+        items.push_back(item);
+    }
+    items
 }
@@ -2,3 +2,4 @@ mod test_backstop_functions;
 mod test_backstop_token_functions;
 mod test_bootstrapper_functions;
 mod test_management;
+mod test_multicall;
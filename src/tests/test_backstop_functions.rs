@@ -1,11 +1,24 @@
 #![cfg(test)]
+#![allow(
+    clippy::inconsistent_digit_grouping,
+    clippy::needless_borrow,
+    clippy::zero_prefixed_literal
+)]
 
+use blend_contract_sdk::backstop::{self, Client as BackstopClient};
 use soroban_sdk::{
-    testutils::{Address as _, EnvTestConfig, MockAuth, MockAuthInvoke},
-    vec, Address, Env, Error, IntoVal,
+    testutils::{
+        Address as _, AuthorizedFunction, AuthorizedInvocation, BytesN as _, EnvTestConfig,
+        MockAuth, MockAuthInvoke,
+    },
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, BytesN, Env, Error, IntoVal, String, Symbol, Vec,
 };
 
+use crate::permissions::{B_DEPOSIT, B_QUEUE, B_WITHDRAW, SCOPE_HIGH, SCOPE_LOW, SCOPE_MEDIUM};
 use crate::testutils::{
+    assert_last_event,
+    contract::{KeeperConfig, LimitKey},
     create_backstop_bootstrapper, create_backstop_manager_wasm, create_blend_contracts,
     EnvTestUtils, ONE_DAY_LEDGERS,
 };
@@ -40,6 +53,7 @@ fn test_execute_backstop_functions() {
         &contracts.backstop_token.address,
         &vec![&e, contracts.backstop.address.clone()],
         &vec![&e, pool.clone()],
+        &0,
     );
 
     // bombadil sent LP tokens to the backstop manager contract
@@ -53,9 +67,11 @@ fn test_execute_backstop_functions() {
     /***** SCOPE 0 *****/
 
     // assert scope is 0
-    let manager = manager_client.manager();
-    assert_eq!(0, manager.scope);
-    assert_eq!(samwise, manager.id);
+    let managers = manager_client.managers();
+    assert_eq!(
+        SCOPE_LOW,
+        managers.get(samwise.clone()).unwrap().permissions
+    );
 
     // deposit - validates contracts
     let deposit_invalid = manager_client
@@ -87,7 +103,7 @@ fn test_execute_backstop_functions() {
 
     // deposit - as owner
     e.set_auths(&[]);
-    manager_client
+    let shares_0 = manager_client
         .mock_auths(&[MockAuth {
             address: &frodo,
             invoke: &MockAuthInvoke {
@@ -105,6 +121,23 @@ fn test_execute_backstop_functions() {
         }])
         .b_deposit(&frodo, &contracts.backstop.address, &pool, &deposit_amount);
     assert_eq!(e.auths()[0].0, frodo); // assert require_auth exists
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "b_deposit",
+        &frodo,
+        (
+            contracts.backstop.address.clone(),
+            pool.clone(),
+            deposit_amount,
+            shares_0,
+        )
+            .into_val(&e),
+    );
+    let record = manager_client.history(&0, &1).get(0).unwrap();
+    assert_eq!(record.action, Symbol::new(&e, "b_deposit"));
+    assert_eq!(record.caller, frodo);
+    assert_eq!(record.amounts, vec![&e, deposit_amount, shares_0]);
     let backstop_bal_0 = contracts
         .backstop
         .user_balance(&pool, &manager_client.address);
@@ -116,7 +149,7 @@ fn test_execute_backstop_functions() {
     // claim - as manager
     let min_lp_amount = 0_1000000;
     e.set_auths(&[]);
-    manager_client
+    let claimed = manager_client
         .mock_auths(&[MockAuth {
             address: &samwise,
             invoke: &MockAuthInvoke {
@@ -134,6 +167,13 @@ fn test_execute_backstop_functions() {
         }])
         .b_claim(&samwise, &contracts.backstop.address, &pool, &min_lp_amount);
     assert_eq!(e.auths()[0].0, samwise); // assert require_auth exists
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "b_claim",
+        &samwise,
+        (contracts.backstop.address.clone(), pool.clone(), claimed).into_val(&e),
+    );
     let backstop_bal_1 = contracts
         .backstop
         .user_balance(&pool, &manager_client.address);
@@ -177,21 +217,23 @@ fn test_execute_backstop_functions() {
             address: &frodo,
             invoke: &MockAuthInvoke {
                 contract: &manager_client.address,
-                fn_name: &"set_manager",
+                fn_name: &"update_manager",
                 args: vec![&e, samwise.into_val(&e), 1u32.into_val(&e)],
                 sub_invokes: &[],
             },
         }])
-        .set_manager(&samwise, &1u32);
+        .update_manager(&samwise, &1u32);
 
     // assert scope is 1
-    let manager = manager_client.manager();
-    assert_eq!(1, manager.scope);
-    assert_eq!(samwise, manager.id);
+    let managers = manager_client.managers();
+    assert_eq!(
+        SCOPE_MEDIUM,
+        managers.get(samwise.clone()).unwrap().permissions
+    );
 
     // deposit - as the manager
     e.set_auths(&[]);
-    manager_client
+    let shares_1 = manager_client
         .mock_auths(&[MockAuth {
             address: &samwise,
             invoke: &MockAuthInvoke {
@@ -214,6 +256,19 @@ fn test_execute_backstop_functions() {
             &deposit_amount,
         );
     assert_eq!(e.auths()[0].0, samwise); // assert require_auth exists
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "b_deposit",
+        &samwise,
+        (
+            contracts.backstop.address.clone(),
+            pool.clone(),
+            deposit_amount,
+            shares_1,
+        )
+            .into_val(&e),
+    );
     let backstop_bal_2 = contracts
         .backstop
         .user_balance(&pool, &manager_client.address);
@@ -265,6 +320,13 @@ fn test_execute_backstop_functions() {
         }])
         .b_queue_withdrawal(&samwise, &contracts.backstop.address, &pool, &q4w_amount);
     assert_eq!(e.auths()[0].0, samwise); // assert require_auth exists
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "b_queue_withdrawal",
+        &samwise,
+        (contracts.backstop.address.clone(), pool.clone(), q4w_amount).into_val(&e),
+    );
     let backstop_bal_3 = contracts
         .backstop
         .user_balance(&pool, &manager_client.address);
@@ -332,6 +394,18 @@ fn test_execute_backstop_functions() {
             &dequeue_amount,
         );
     assert_eq!(e.auths()[0].0, samwise); // assert require_auth exists
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "b_dequeue_withdrawal",
+        &samwise,
+        (
+            contracts.backstop.address.clone(),
+            pool.clone(),
+            dequeue_amount,
+        )
+            .into_val(&e),
+    );
     let backstop_bal_4 = contracts
         .backstop
         .user_balance(&pool, &manager_client.address);
@@ -379,17 +453,19 @@ fn test_execute_backstop_functions() {
             address: &frodo,
             invoke: &MockAuthInvoke {
                 contract: &manager_client.address,
-                fn_name: &"set_manager",
+                fn_name: &"update_manager",
                 args: vec![&e, samwise.into_val(&e), 2u32.into_val(&e)],
                 sub_invokes: &[],
             },
         }])
-        .set_manager(&samwise, &2u32);
+        .update_manager(&samwise, &2u32);
 
     // assert scope is 2
-    let manager = manager_client.manager();
-    assert_eq!(2, manager.scope);
-    assert_eq!(samwise, manager.id);
+    let managers = manager_client.managers();
+    assert_eq!(
+        SCOPE_HIGH,
+        managers.get(samwise.clone()).unwrap().permissions
+    );
 
     // withdraw - validates contracts
     e.set_auths(&[]);
@@ -418,7 +494,7 @@ fn test_execute_backstop_functions() {
     // withdraw - as the manager
     let backstop_token_bal_0 = contracts.backstop_token.balance(&manager_client.address);
     e.set_auths(&[]);
-    manager_client
+    let tokens_out = manager_client
         .mock_auths(&[MockAuth {
             address: &samwise,
             invoke: &MockAuthInvoke {
@@ -436,6 +512,19 @@ fn test_execute_backstop_functions() {
         }])
         .b_withdraw(&samwise, &contracts.backstop.address, &pool, &q4w.amount);
     assert_eq!(e.auths()[0].0, samwise); // assert require_auth exists
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "b_withdraw",
+        &samwise,
+        (
+            contracts.backstop.address.clone(),
+            pool.clone(),
+            q4w.amount,
+            tokens_out,
+        )
+            .into_val(&e),
+    );
     let backstop_bal_5 = contracts
         .backstop
         .user_balance(&pool, &manager_client.address);
@@ -474,3 +563,1546 @@ fn test_execute_backstop_functions() {
         .user_balance(&pool, &manager_client.address);
     assert!(backstop_bal_6.shares > backstop_bal_5.shares);
 }
+
+#[test]
+fn test_withdraw_limit() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let usdc = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let blnd = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let (contracts, pool) = create_blend_contracts(&e, &bombadil, &blnd.address(), &usdc.address());
+    let bootstrapper = create_backstop_bootstrapper(&e, &contracts);
+
+    // start manager (samwise) at scope 2
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &samwise,
+        &2,
+        &bootstrapper.address,
+        &contracts.backstop_token.address,
+        &vec![&e, contracts.backstop.address.clone()],
+        &vec![&e, pool.clone()],
+        &0,
+    );
+
+    // bombadil sent LP tokens to the backstop manager contract
+    let lp_mint_amount = 1_000_0000000;
+    contracts
+        .backstop_token
+        .transfer(&bombadil, &manager_client.address, &lp_mint_amount);
+
+    // deposit and queue all shares for withdrawal
+    manager_client.b_deposit(&frodo, &contracts.backstop.address, &pool, &lp_mint_amount);
+    manager_client.b_queue_withdrawal(&frodo, &contracts.backstop.address, &pool, &lp_mint_amount);
+    e.jump(17 * ONE_DAY_LEDGERS + 1);
+
+    // limit managers to withdrawing 400 backstop tokens per week
+    let max_amount: i128 = 400_0000000;
+    manager_client.set_limit(&LimitKey::Withdraw, &max_amount, &(7 * ONE_DAY_LEDGERS));
+
+    // withdraw - validate manager cannot exceed the limit
+    let result = manager_client.try_b_withdraw(
+        &samwise,
+        &contracts.backstop.address,
+        &pool,
+        &(max_amount + 1),
+    );
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(112))));
+
+    // withdraw - as manager up to the limit
+    let tokens_out =
+        manager_client.b_withdraw(&samwise, &contracts.backstop.address, &pool, &max_amount);
+    assert_eq!(tokens_out, max_amount);
+    assert_eq!(manager_client.remaining_limit(&LimitKey::Withdraw), Some(0));
+    let result = manager_client.try_b_withdraw(&samwise, &contracts.backstop.address, &pool, &1);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(112))));
+
+    // withdraw - as owner is not limited
+    manager_client.b_withdraw(
+        &frodo,
+        &contracts.backstop.address,
+        &pool,
+        &(max_amount + 1),
+    );
+    assert_eq!(manager_client.remaining_limit(&LimitKey::Withdraw), Some(0));
+
+    // withdraw - as manager after the period resets
+    e.jump(7 * ONE_DAY_LEDGERS);
+    let remaining_shares = lp_mint_amount - 2 * max_amount - 1;
+    manager_client.b_withdraw(
+        &samwise,
+        &contracts.backstop.address,
+        &pool,
+        &remaining_shares,
+    );
+    assert_eq!(
+        manager_client.remaining_limit(&LimitKey::Withdraw),
+        Some(max_amount - remaining_shares)
+    );
+    assert_eq!(
+        contracts.backstop_token.balance(&manager_client.address),
+        lp_mint_amount
+    );
+}
+
+#[test]
+fn test_positions() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let usdc = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let blnd = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let (contracts, pool) = create_blend_contracts(&e, &bombadil, &blnd.address(), &usdc.address());
+    let bootstrapper = create_backstop_bootstrapper(&e, &contracts);
+    let other_pool = Address::generate(&e);
+
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &samwise,
+        &2,
+        &bootstrapper.address,
+        &contracts.backstop_token.address,
+        &vec![&e, contracts.backstop.address.clone()],
+        &vec![&e, pool.clone(), other_pool.clone()],
+        &0,
+    );
+
+    // no deposits are held
+    let positions = manager_client.positions(&0, &10);
+    assert_eq!(positions.len(), 2);
+    let position = positions.get(0).unwrap();
+    assert_eq!(position.backstop, contracts.backstop.address);
+    assert_eq!(position.pool, pool);
+    assert_eq!(position.shares, 0);
+    assert_eq!(position.tokens, 0);
+    assert_eq!(position.q4w.len(), 0);
+    assert_eq!(position.q4w_tokens, 0);
+
+    // deposit and queue part of the shares for withdrawal
+    let lp_mint_amount = 1_000_0000000;
+    contracts
+        .backstop_token
+        .transfer(&bombadil, &manager_client.address, &lp_mint_amount);
+    let shares =
+        manager_client.b_deposit(&frodo, &contracts.backstop.address, &pool, &lp_mint_amount);
+    let q4w_amount = shares / 4;
+    manager_client.b_queue_withdrawal(&frodo, &contracts.backstop.address, &pool, &q4w_amount);
+
+    let pool_data = contracts.backstop.pool_data(&pool);
+    let balance = contracts
+        .backstop
+        .user_balance(&pool, &manager_client.address);
+    let positions = manager_client.positions(&0, &10);
+    assert_eq!(positions.len(), 2);
+    let position = positions.get(0).unwrap();
+    assert_eq!(position.pool, pool);
+    assert_eq!(position.shares, shares - q4w_amount);
+    assert_eq!(
+        position.tokens,
+        (shares - q4w_amount) * pool_data.tokens / pool_data.shares
+    );
+    assert_eq!(position.q4w.len(), 1);
+    let q4w = position.q4w.get(0).unwrap();
+    assert_eq!(q4w.amount, q4w_amount);
+    assert_eq!(q4w.exp, balance.q4w.get(0).unwrap().exp);
+    assert_eq!(
+        position.q4w_tokens,
+        q4w_amount * pool_data.tokens / pool_data.shares
+    );
+    let position = positions.get(1).unwrap();
+    assert_eq!(position.pool, other_pool);
+    assert_eq!(position.shares, 0);
+    assert_eq!(position.q4w.len(), 0);
+
+    // positions are paginated in the same order as pairs
+    let positions = manager_client.positions(&1, &1);
+    assert_eq!(positions.len(), 1);
+    assert_eq!(positions.get(0).unwrap().pool, other_pool);
+    assert_eq!(manager_client.positions(&2, &10).len(), 0);
+}
+
+#[test]
+fn test_net_asset_value() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let usdc = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let blnd = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let (contracts, pool) = create_blend_contracts(&e, &bombadil, &blnd.address(), &usdc.address());
+    let bootstrapper = create_backstop_bootstrapper(&e, &contracts);
+
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &samwise,
+        &2,
+        &bootstrapper.address,
+        &contracts.backstop_token.address,
+        &vec![&e, contracts.backstop.address.clone()],
+        &vec![&e, pool.clone(), Address::generate(&e)],
+        &0,
+    );
+
+    // nothing is held
+    let nav = manager_client.net_asset_value(&0, &10);
+    assert_eq!(nav.lp_held, 0);
+    assert_eq!(nav.lp_deposited, 0);
+    assert_eq!(nav.assets.len(), 2);
+    for asset in nav.assets.iter() {
+        assert_eq!(asset.held, 0);
+        assert_eq!(asset.lp_value, 0);
+        assert_eq!(asset.total, 0);
+    }
+
+    // hold idle BLND and LP tokens, and deposit and queue LP tokens into the backstop
+    let blnd_held = 500_0000000;
+    StellarAssetClient::new(&e, &blnd.address()).mint(&manager_client.address, &blnd_held);
+    let lp_amount = 1_000_0000000;
+    contracts
+        .backstop_token
+        .transfer(&bombadil, &manager_client.address, &lp_amount);
+    let deposit_amount = 600_0000000;
+    let shares =
+        manager_client.b_deposit(&frodo, &contracts.backstop.address, &pool, &deposit_amount);
+    manager_client.b_queue_withdrawal(&frodo, &contracts.backstop.address, &pool, &(shares / 3));
+
+    let position = manager_client.positions(&0, &1).get(0).unwrap();
+    let lp_held = lp_amount - deposit_amount;
+    let lp_deposited = position.tokens + position.q4w_tokens;
+    assert!(lp_deposited > 0 && lp_deposited <= deposit_amount);
+
+    let nav = manager_client.net_asset_value(&0, &10);
+    assert_eq!(nav.lp_held, lp_held);
+    assert_eq!(nav.lp_deposited, lp_deposited);
+    let lp_supply = contracts.backstop_token.get_total_supply();
+    for asset in nav.assets.iter() {
+        let lp_value = (lp_held + lp_deposited)
+            * contracts.backstop_token.get_balance(&asset.token)
+            / lp_supply;
+        let held = if asset.token == blnd.address() {
+            blnd_held
+        } else {
+            assert_eq!(asset.token, usdc.address());
+            0
+        };
+        assert!(lp_value > 0);
+        assert_eq!(asset.held, held);
+        assert_eq!(asset.lp_value, lp_value);
+        assert_eq!(asset.total, held + lp_value);
+    }
+
+    // pages - held tokens are only included in the first page, and the pages sum to the total
+    let first_page = manager_client.net_asset_value(&0, &1);
+    assert_eq!(first_page.lp_held, lp_held);
+    assert_eq!(first_page.lp_deposited, lp_deposited);
+    let second_page = manager_client.net_asset_value(&1, &1);
+    assert_eq!(second_page.lp_held, 0);
+    assert_eq!(second_page.lp_deposited, 0);
+    for (i, asset) in second_page.assets.iter().enumerate() {
+        assert_eq!(asset.held, 0);
+        assert_eq!(asset.total, 0);
+        assert_eq!(
+            first_page.assets.get(i as u32).unwrap().total,
+            nav.assets.get(i as u32).unwrap().total
+        );
+    }
+    let past_end = manager_client.net_asset_value(&2, &10);
+    assert_eq!(past_end.lp_held, 0);
+    assert_eq!(past_end.lp_deposited, 0);
+}
+
+#[test]
+fn test_claimed_totals() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let usdc = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let blnd = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let (contracts, pool) = create_blend_contracts(&e, &bombadil, &blnd.address(), &usdc.address());
+    let bootstrapper = create_backstop_bootstrapper(&e, &contracts);
+    let blnd_token = TokenClient::new(&e, &blnd.address());
+    let other_pool = Address::generate(&e);
+
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &samwise,
+        &2,
+        &bootstrapper.address,
+        &contracts.backstop_token.address,
+        &vec![&e, contracts.backstop.address.clone()],
+        &vec![&e, pool.clone(), other_pool.clone()],
+        &0,
+    );
+
+    let lp_mint_amount = 1_000_0000000;
+    contracts
+        .backstop_token
+        .transfer(&bombadil, &manager_client.address, &lp_mint_amount);
+    manager_client.b_deposit(&frodo, &contracts.backstop.address, &pool, &lp_mint_amount);
+
+    let total = manager_client.claimed(&contracts.backstop.address, &pool);
+    assert_eq!(total.amount, 0);
+    assert_eq!(total.blnd, 0);
+    assert_eq!(total.period_start, 0);
+    assert_eq!(total.last_amount, 0);
+    assert_eq!(total.total_amount, 0);
+
+    // claims are added to the totals of the pair
+    let comet_blnd_0 = blnd_token.balance(&contracts.backstop_token.address);
+    e.jump(ONE_DAY_LEDGERS);
+    let claimed_0 = manager_client.b_claim(&samwise, &contracts.backstop.address, &pool, &0);
+    e.jump(ONE_DAY_LEDGERS);
+    let claimed_1 = manager_client.b_claim(&frodo, &contracts.backstop.address, &pool, &0);
+    assert!(claimed_0 > 0 && claimed_1 > 0);
+    let blnd_claimed = blnd_token.balance(&contracts.backstop_token.address) - comet_blnd_0;
+    assert!(blnd_claimed > 0);
+    let total = manager_client.claimed(&contracts.backstop.address, &pool);
+    assert_eq!(total.amount, claimed_0 + claimed_1);
+    assert_eq!(total.blnd, blnd_claimed);
+    assert_eq!(total.period_start, 0);
+    assert_eq!(total.last_amount, 0);
+    assert_eq!(total.last_blnd, 0);
+    assert_eq!(total.total_amount, claimed_0 + claimed_1);
+    assert_eq!(total.total_blnd, blnd_claimed);
+    let other_total = manager_client.claimed(&contracts.backstop.address, &other_pool);
+    assert_eq!(other_total.amount, 0);
+    assert_eq!(other_total.blnd, 0);
+
+    // snapshot - ends the current period
+    let snapshot = manager_client.snapshot_claimed(&contracts.backstop.address, &pool);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "snapshot_claimed",
+        &frodo,
+        (
+            contracts.backstop.address.clone(),
+            pool.clone(),
+            claimed_0 + claimed_1,
+            blnd_claimed,
+        )
+            .into_val(&e),
+    );
+    assert_eq!(snapshot.amount, 0);
+    assert_eq!(snapshot.blnd, 0);
+    assert_eq!(snapshot.last_amount, claimed_0 + claimed_1);
+    assert_eq!(snapshot.last_blnd, blnd_claimed);
+    assert_eq!(snapshot.total_amount, claimed_0 + claimed_1);
+    assert_eq!(snapshot.total_blnd, blnd_claimed);
+    assert_eq!(
+        e.auths()[0],
+        (
+            frodo.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "snapshot_claimed"),
+                    vec![
+                        &e,
+                        contracts.backstop.address.into_val(&e),
+                        pool.into_val(&e)
+                    ]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    let total = manager_client.claimed(&contracts.backstop.address, &pool);
+    assert_eq!(total.amount, 0);
+    assert_eq!(total.blnd, 0);
+    assert_eq!(total.period_start, e.ledger().sequence());
+    assert_eq!(total.last_amount, claimed_0 + claimed_1);
+    assert_eq!(total.last_blnd, blnd_claimed);
+
+    // claims after the snapshot start a new period, and add to the cumulative totals
+    e.jump(ONE_DAY_LEDGERS);
+    let comet_blnd_1 = blnd_token.balance(&contracts.backstop_token.address);
+    let claimed_2 = manager_client.b_claim(&samwise, &contracts.backstop.address, &pool, &0);
+    let blnd_claimed_2 = blnd_token.balance(&contracts.backstop_token.address) - comet_blnd_1;
+    let total = manager_client.claimed(&contracts.backstop.address, &pool);
+    assert_eq!(total.amount, claimed_2);
+    assert_eq!(total.blnd, blnd_claimed_2);
+    assert_eq!(total.last_amount, claimed_0 + claimed_1);
+    assert_eq!(total.last_blnd, blnd_claimed);
+    assert_eq!(total.total_amount, claimed_0 + claimed_1 + claimed_2);
+    assert_eq!(total.total_blnd, blnd_claimed + blnd_claimed_2);
+
+    // snapshot - validate only the owner can snapshot
+    let result = manager_client
+        .mock_auths(&[MockAuth {
+            address: &samwise,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: &"snapshot_claimed",
+                args: vec![
+                    &e,
+                    contracts.backstop.address.into_val(&e),
+                    pool.into_val(&e),
+                ],
+                sub_invokes: &[],
+            },
+        }])
+        .try_snapshot_claimed(&contracts.backstop.address, &pool);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_withdraw_unlocked() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let usdc = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let blnd = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let (contracts, pool) = create_blend_contracts(&e, &bombadil, &blnd.address(), &usdc.address());
+    let bootstrapper = create_backstop_bootstrapper(&e, &contracts);
+
+    // start manager (samwise) at scope 1
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &samwise,
+        &1,
+        &bootstrapper.address,
+        &contracts.backstop_token.address,
+        &vec![&e, contracts.backstop.address.clone()],
+        &vec![&e, pool.clone()],
+        &0,
+    );
+
+    let lp_mint_amount = 1_000_0000000;
+    contracts
+        .backstop_token
+        .transfer(&bombadil, &manager_client.address, &lp_mint_amount);
+    let shares =
+        manager_client.b_deposit(&frodo, &contracts.backstop.address, &pool, &lp_mint_amount);
+
+    // queue two withdrawals 10 days apart
+    let q4w_amount_0 = shares / 4;
+    let q4w_amount_1 = shares / 2;
+    manager_client.b_queue_withdrawal(&samwise, &contracts.backstop.address, &pool, &q4w_amount_0);
+    e.jump(10 * ONE_DAY_LEDGERS);
+    manager_client.b_queue_withdrawal(&samwise, &contracts.backstop.address, &pool, &q4w_amount_1);
+
+    // withdraw unlocked - nothing has unlocked
+    let tokens_out = manager_client.b_withdraw_unlocked(&frodo, &contracts.backstop.address, &pool);
+    assert_eq!(tokens_out, 0);
+    let backstop_bal = contracts
+        .backstop
+        .user_balance(&pool, &manager_client.address);
+    assert_eq!(backstop_bal.q4w.len(), 2);
+
+    // withdraw unlocked - validate scope 1 cannot withdraw
+    e.jump(8 * ONE_DAY_LEDGERS);
+    e.set_auths(&[]);
+    let result = manager_client
+        .mock_auths(&[MockAuth {
+            address: &samwise,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: &"b_withdraw_unlocked",
+                args: vec![
+                    &e,
+                    samwise.into_val(&e),
+                    contracts.backstop.address.into_val(&e),
+                    pool.into_val(&e),
+                ],
+                sub_invokes: &[],
+            },
+        }])
+        .try_b_withdraw_unlocked(&samwise, &contracts.backstop.address, &pool);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(4))));
+
+    // withdraw unlocked - validates contracts
+    e.mock_all_auths();
+    let result = manager_client.try_b_withdraw_unlocked(&frodo, &Address::generate(&e), &pool);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(101))));
+
+    // withdraw unlocked - only the first withdrawal has unlocked
+    let token_balance_0 = contracts.backstop_token.balance(&manager_client.address);
+    let tokens_out = manager_client.b_withdraw_unlocked(&frodo, &contracts.backstop.address, &pool);
+    assert_eq!(tokens_out, q4w_amount_0);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "b_withdraw_unlocked",
+        &frodo,
+        (
+            contracts.backstop.address.clone(),
+            pool.clone(),
+            q4w_amount_0,
+            tokens_out,
+        )
+            .into_val(&e),
+    );
+    assert_eq!(
+        contracts.backstop_token.balance(&manager_client.address),
+        token_balance_0 + tokens_out
+    );
+    let backstop_bal = contracts
+        .backstop
+        .user_balance(&pool, &manager_client.address);
+    assert_eq!(backstop_bal.q4w.len(), 1);
+    assert_eq!(backstop_bal.q4w.get(0).unwrap().amount, q4w_amount_1);
+    let record = manager_client.history(&0, &1).get(0).unwrap();
+    assert_eq!(record.action, Symbol::new(&e, "b_withdraw_unlocked"));
+    assert_eq!(record.amounts, vec![&e, q4w_amount_0, tokens_out]);
+
+    // withdraw unlocked - the second withdrawal unlocks later
+    e.jump(10 * ONE_DAY_LEDGERS);
+    let tokens_out = manager_client.b_withdraw_unlocked(&frodo, &contracts.backstop.address, &pool);
+    assert_eq!(tokens_out, q4w_amount_1);
+    let backstop_bal = contracts
+        .backstop
+        .user_balance(&pool, &manager_client.address);
+    assert_eq!(backstop_bal.q4w.len(), 0);
+    assert_eq!(backstop_bal.shares, shares - q4w_amount_0 - q4w_amount_1);
+}
+
+#[test]
+fn test_compound() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let usdc = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let blnd = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let (contracts, pool) = create_blend_contracts(&e, &bombadil, &blnd.address(), &usdc.address());
+    let bootstrapper = create_backstop_bootstrapper(&e, &contracts);
+
+    // add a second pool to the reward zone
+    let other_pool = contracts.pool_factory.deploy(
+        &bombadil,
+        &String::from_str(&e, "other"),
+        &BytesN::<32>::random(&e),
+        &Address::generate(&e),
+        &0,
+        &2,
+        &0,
+    );
+    contracts
+        .backstop
+        .deposit(&bombadil, &other_pool, &50_000_0000000);
+    contracts.backstop.add_reward(&other_pool, &None);
+    e.jump(7 * ONE_DAY_LEDGERS);
+    contracts.emitter.distribute();
+    contracts.backstop.distribute();
+    contracts.backstop.gulp_emissions(&pool);
+    contracts.backstop.gulp_emissions(&other_pool);
+
+    // start manager (samwise) at scope 0
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &samwise,
+        &0,
+        &bootstrapper.address,
+        &contracts.backstop_token.address,
+        &vec![&e, contracts.backstop.address.clone()],
+        &vec![&e, pool.clone(), other_pool.clone()],
+        &0,
+    );
+
+    // deposit into both pools
+    let lp_mint_amount = 1_000_0000000;
+    contracts
+        .backstop_token
+        .transfer(&bombadil, &manager_client.address, &lp_mint_amount);
+    let shares_0 = manager_client.b_deposit(
+        &frodo,
+        &contracts.backstop.address,
+        &pool,
+        &(lp_mint_amount / 2),
+    );
+    let other_shares_0 = manager_client.b_deposit(
+        &frodo,
+        &contracts.backstop.address,
+        &other_pool,
+        &(lp_mint_amount / 2),
+    );
+    e.jump(ONE_DAY_LEDGERS);
+    let pools = vec![&e, pool.clone(), other_pool.clone()];
+
+    // compound - validates contracts
+    let result = manager_client.try_b_compound(
+        &samwise,
+        &contracts.backstop.address,
+        &vec![&e, pool.clone(), Address::generate(&e)],
+        &0,
+    );
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(101))));
+
+    // compound - validates the minimum backstop tokens out
+    let result = manager_client.try_b_compound(
+        &samwise,
+        &contracts.backstop.address,
+        &pools,
+        &1_000_000_0000000,
+    );
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(125))));
+
+    // compound - validate a manager without B_CLAIM cannot compound
+    manager_client.set_manager_permissions(&samwise, &B_DEPOSIT);
+    let result = manager_client.try_b_compound(&samwise, &contracts.backstop.address, &pools, &0);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(4))));
+    manager_client.set_manager_permissions(&samwise, &SCOPE_LOW);
+
+    // compound - as manager
+    let min_lp_amount = 0_1000000;
+    e.set_auths(&[]);
+    let claimed = manager_client
+        .mock_auths(&[MockAuth {
+            address: &samwise,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: &"b_compound",
+                args: vec![
+                    &e,
+                    samwise.into_val(&e),
+                    contracts.backstop.address.into_val(&e),
+                    pools.into_val(&e),
+                    min_lp_amount.into_val(&e),
+                ],
+                sub_invokes: &[],
+            },
+        }])
+        .b_compound(
+            &samwise,
+            &contracts.backstop.address,
+            &pools,
+            &min_lp_amount,
+        );
+    // the manager's auth is the only auth in the tree. The contract invokes each claim
+    // directly, and the backstop deposits the minted backstop tokens itself, so no deposit or
+    // token transfer has to be authorized by the contract
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            samwise.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "b_compound"),
+                    vec![
+                        &e,
+                        samwise.into_val(&e),
+                        contracts.backstop.address.into_val(&e),
+                        pools.into_val(&e),
+                        min_lp_amount.into_val(&e),
+                    ]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "b_compound",
+        &samwise,
+        (contracts.backstop.address.clone(), pools.clone(), claimed).into_val(&e),
+    );
+
+    // emissions of each pool were deposited back into that pool
+    let claimed_0 = manager_client
+        .claimed(&contracts.backstop.address, &pool)
+        .amount;
+    let claimed_1 = manager_client
+        .claimed(&contracts.backstop.address, &other_pool)
+        .amount;
+    assert!(claimed_0 > 0 && claimed_1 > 0);
+    assert_eq!(claimed, claimed_0 + claimed_1);
+    assert!(claimed >= min_lp_amount);
+    assert_eq!(contracts.backstop_token.balance(&manager_client.address), 0);
+    let pool_data = contracts.backstop.pool_data(&pool);
+    let backstop_bal = contracts
+        .backstop
+        .user_balance(&pool, &manager_client.address);
+    assert_eq!(
+        backstop_bal.shares - shares_0,
+        claimed_0 * pool_data.shares / pool_data.tokens
+    );
+    let pool_data = contracts.backstop.pool_data(&other_pool);
+    let backstop_bal = contracts
+        .backstop
+        .user_balance(&other_pool, &manager_client.address);
+    assert_eq!(
+        backstop_bal.shares - other_shares_0,
+        claimed_1 * pool_data.shares / pool_data.tokens
+    );
+    let record = manager_client.history(&0, &1).get(0).unwrap();
+    assert_eq!(record.action, Symbol::new(&e, "b_compound"));
+    assert_eq!(record.amounts, vec![&e, claimed]);
+}
+
+#[test]
+fn test_keeper_compound() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let merry = Address::generate(&e);
+    let usdc = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let blnd = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let (contracts, pool) = create_blend_contracts(&e, &bombadil, &blnd.address(), &usdc.address());
+    let bootstrapper = create_backstop_bootstrapper(&e, &contracts);
+
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &samwise,
+        &0,
+        &bootstrapper.address,
+        &contracts.backstop_token.address,
+        &vec![&e, contracts.backstop.address.clone()],
+        &vec![&e, pool.clone()],
+        &0,
+    );
+
+    // deposit every backstop token, so none are held idle by the contract
+    let lp_mint_amount = 1_000_0000000;
+    contracts
+        .backstop_token
+        .transfer(&bombadil, &manager_client.address, &lp_mint_amount);
+    manager_client.b_deposit(&frodo, &contracts.backstop.address, &pool, &lp_mint_amount);
+    e.jump(ONE_DAY_LEDGERS);
+
+    // keeper compound - validate keepers are enabled
+    assert!(manager_client.keeper().is_none());
+    let result = manager_client.try_keeper_compound(&merry, &pool);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(127))));
+
+    // set keeper - validate the config
+    let mut config = KeeperConfig {
+        backstop: contracts.backstop.address.clone(),
+        interval: 7 * ONE_DAY_LEDGERS,
+        min_lp_out_ratio: 0_0100000,
+        bounty: 0_0100000,
+    };
+    config.interval = 0;
+    let result = manager_client.try_set_keeper(&config);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(126))));
+    config.interval = 7 * ONE_DAY_LEDGERS;
+    config.bounty = 0_1000001;
+    let result = manager_client.try_set_keeper(&config);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(126))));
+    config.bounty = 0_0100000;
+    config.min_lp_out_ratio = 1_000_000_0000001;
+    let result = manager_client.try_set_keeper(&config);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(126))));
+    config.min_lp_out_ratio = 0_0100000;
+
+    // set keeper
+    manager_client.set_keeper(&config);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "set_keeper",
+        &frodo,
+        config.clone().into_val(&e),
+    );
+    assert_eq!(
+        e.auths()[0],
+        (
+            frodo.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "set_keeper"),
+                    vec![&e, config.clone().into_val(&e)]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    assert_eq!(manager_client.keeper().unwrap().bounty, config.bounty);
+
+    // keeper compound - validates contracts
+    let result = manager_client.try_keeper_compound(&merry, &Address::generate(&e));
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(101))));
+
+    // keeper compound - no bounty is paid while the budget is unfunded
+    let claimed = manager_client.keeper_compound(&merry, &pool);
+    assert!(claimed > 0);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "keeper_compound",
+        &merry,
+        (
+            contracts.backstop.address.clone(),
+            pool.clone(),
+            claimed,
+            0i128,
+        )
+            .into_val(&e),
+    );
+    assert_eq!(contracts.backstop_token.balance(&merry), 0);
+    assert_eq!(contracts.backstop_token.balance(&manager_client.address), 0);
+    let claimed_0 = claimed;
+    e.jump(7 * ONE_DAY_LEDGERS);
+
+    // fund keeper
+    let budget = 100_0000000;
+    contracts
+        .backstop_token
+        .transfer(&bombadil, &frodo, &budget);
+    let result = manager_client.try_fund_keeper(&0);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(8))));
+    assert_eq!(manager_client.fund_keeper(&budget), budget);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "fund_keeper",
+        &frodo,
+        (budget, budget).into_val(&e),
+    );
+    assert_eq!(e.auths()[0].0, frodo);
+    assert_eq!(manager_client.keeper_budget(), budget);
+    assert_eq!(contracts.backstop_token.balance(&frodo), 0);
+
+    // keeper compound - anyone can compound and receive the bounty
+    let shares_0 = contracts
+        .backstop
+        .user_balance(&pool, &manager_client.address)
+        .shares;
+    e.set_auths(&[]);
+    let claimed = manager_client
+        .mock_auths(&[MockAuth {
+            address: &merry,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: &"keeper_compound",
+                args: vec![&e, merry.into_val(&e), pool.into_val(&e)],
+                sub_invokes: &[],
+            },
+        }])
+        .keeper_compound(&merry, &pool);
+    assert_eq!(e.auths()[0].0, merry); // assert require_auth exists
+    assert!(claimed > 0);
+    let bounty = claimed * config.bounty / 1_0000000;
+    assert!(bounty > 0);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "keeper_compound",
+        &merry,
+        (
+            contracts.backstop.address.clone(),
+            pool.clone(),
+            claimed,
+            bounty,
+        )
+            .into_val(&e),
+    );
+    assert_eq!(contracts.backstop_token.balance(&merry), bounty);
+    assert_eq!(
+        contracts.backstop_token.balance(&manager_client.address),
+        budget - bounty
+    );
+    assert_eq!(manager_client.keeper_budget(), budget - bounty);
+    assert!(
+        contracts
+            .backstop
+            .user_balance(&pool, &manager_client.address)
+            .shares
+            > shares_0
+    );
+    assert_eq!(
+        manager_client
+            .claimed(&contracts.backstop.address, &pool)
+            .amount,
+        claimed_0 + claimed
+    );
+    let record = manager_client.history(&0, &1).get(0).unwrap();
+    assert_eq!(record.action, Symbol::new(&e, "keeper_compound"));
+    assert_eq!(record.caller, merry);
+    assert_eq!(record.amounts, vec![&e, claimed, bounty]);
+
+    // keeper compound - validate the interval has passed
+    e.mock_all_auths();
+    e.jump(7 * ONE_DAY_LEDGERS - 1);
+    let result = manager_client.try_keeper_compound(&merry, &pool);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(128))));
+
+    // keeper compound - validate the minimum LP-out ratio
+    contracts.emitter.distribute();
+    contracts.backstop.distribute();
+    contracts.backstop.gulp_emissions(&pool);
+    e.jump(1);
+    config.min_lp_out_ratio = 1_000_000_0000000;
+    manager_client.set_keeper(&config);
+    let result = manager_client.try_keeper_compound(&merry, &pool);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(125))));
+    config.min_lp_out_ratio = 0_0100000;
+    config.bounty = 0;
+    manager_client.set_keeper(&config);
+
+    // keeper compound - validate keepers respect the pause
+    let pippin = Address::generate(&e);
+    manager_client.set_guardian(&Some(pippin));
+    manager_client.pause();
+    let result = manager_client.try_keeper_compound(&merry, &pool);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(115))));
+    manager_client.unpause();
+
+    // keeper compound - no bounty is paid if none is set
+    let claimed = manager_client.keeper_compound(&merry, &pool);
+    assert!(claimed > 0);
+    assert_eq!(contracts.backstop_token.balance(&merry), bounty);
+
+    // remove keeper
+    manager_client.remove_keeper();
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "remove_keeper",
+        &frodo,
+        ().into_val(&e),
+    );
+    assert!(manager_client.keeper().is_none());
+    assert_eq!(manager_client.keeper_budget(), 0);
+    e.jump(7 * ONE_DAY_LEDGERS);
+    let result = manager_client.try_keeper_compound(&merry, &pool);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(127))));
+    let result = manager_client.try_remove_keeper();
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(127))));
+}
+
+#[test]
+fn test_rebalance() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let usdc = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let blnd = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let (contracts, pool) = create_blend_contracts(&e, &bombadil, &blnd.address(), &usdc.address());
+    let bootstrapper = create_backstop_bootstrapper(&e, &contracts);
+
+    let other_pool = contracts.pool_factory.deploy(
+        &bombadil,
+        &String::from_str(&e, "other"),
+        &BytesN::<32>::random(&e),
+        &Address::generate(&e),
+        &0,
+        &2,
+        &0,
+    );
+
+    // start manager (samwise) at scope 0
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &samwise,
+        &0,
+        &bootstrapper.address,
+        &contracts.backstop_token.address,
+        &vec![&e, contracts.backstop.address.clone()],
+        &vec![&e, pool.clone(), other_pool.clone()],
+        &0,
+    );
+
+    let lp_mint_amount = 1_000_0000000;
+    contracts
+        .backstop_token
+        .transfer(&bombadil, &manager_client.address, &lp_mint_amount);
+    let shares =
+        manager_client.b_deposit(&frodo, &contracts.backstop.address, &pool, &lp_mint_amount);
+
+    // rebalance - validate queue permission alone cannot record a plan
+    manager_client.set_manager_permissions(&samwise, &B_QUEUE);
+    let result = manager_client.try_b_rebalance(
+        &samwise,
+        &contracts.backstop.address,
+        &pool,
+        &other_pool,
+        &(shares / 2),
+    );
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(4))));
+
+    // rebalance - validates pools
+    manager_client.set_manager_permissions(&samwise, &SCOPE_MEDIUM);
+    let result = manager_client.try_b_rebalance(
+        &samwise,
+        &contracts.backstop.address,
+        &pool,
+        &pool,
+        &(shares / 2),
+    );
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(101))));
+    let result = manager_client.try_b_rebalance(
+        &samwise,
+        &contracts.backstop.address,
+        &pool,
+        &Address::generate(&e),
+        &(shares / 2),
+    );
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(101))));
+
+    // rebalance - record two plans
+    let amount_0 = shares / 2;
+    let amount_1 = shares / 4;
+    let plan_id_0 = manager_client.b_rebalance(
+        &samwise,
+        &contracts.backstop.address,
+        &pool,
+        &other_pool,
+        &amount_0,
+    );
+    assert_eq!(plan_id_0, 0);
+    assert_eq!(
+        e.auths()[0],
+        (
+            samwise.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "b_rebalance"),
+                    vec![
+                        &e,
+                        samwise.into_val(&e),
+                        contracts.backstop.address.into_val(&e),
+                        pool.into_val(&e),
+                        other_pool.into_val(&e),
+                        amount_0.into_val(&e),
+                    ]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    let plan_id_1 = manager_client.b_rebalance(
+        &samwise,
+        &contracts.backstop.address,
+        &pool,
+        &other_pool,
+        &amount_1,
+    );
+    assert_eq!(plan_id_1, 1);
+    let record = manager_client.history(&0, &1).get(0).unwrap();
+    assert_eq!(record.action, Symbol::new(&e, "b_rebalance"));
+    assert_eq!(record.amounts, vec![&e, plan_id_1 as i128, amount_1]);
+
+    let plans = manager_client.rebalances(&0, &10);
+    assert_eq!(plans.len(), 2);
+    let plan_0 = plans.get(0).unwrap();
+    assert_eq!(plan_0.id, plan_id_0);
+    assert_eq!(plan_0.backstop, contracts.backstop.address);
+    assert_eq!(plan_0.source_pool, pool);
+    assert_eq!(plan_0.destination_pool, other_pool);
+    assert_eq!(plan_0.shares, amount_0);
+    let backstop_bal = contracts
+        .backstop
+        .user_balance(&pool, &manager_client.address);
+    assert_eq!(backstop_bal.q4w.len(), 2);
+    assert_eq!(backstop_bal.q4w.get(0).unwrap().exp, plan_0.exp);
+
+    // execute - shares are still locked
+    let keeper = Address::generate(&e);
+    let result = manager_client.try_execute_rebalance(&keeper, &plan_id_0);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(130))));
+
+    // cancel - dequeues the shares
+    manager_client.cancel_rebalance(&samwise, &plan_id_1);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "cancel_rebalance",
+        &samwise,
+        plans.get(1).unwrap().into_val(&e),
+    );
+    let backstop_bal = contracts
+        .backstop
+        .user_balance(&pool, &manager_client.address);
+    assert_eq!(backstop_bal.q4w.len(), 1);
+    assert_eq!(backstop_bal.shares, shares - amount_0);
+    assert_eq!(manager_client.rebalances(&0, &10).len(), 1);
+    let result = manager_client.try_cancel_rebalance(&samwise, &plan_id_1);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(129))));
+
+    // execute - anyone can execute once the shares unlock
+    e.jump(17 * ONE_DAY_LEDGERS + 1);
+    let pool_data = contracts.backstop.pool_data(&pool);
+    let tokens = amount_0 * pool_data.tokens / pool_data.shares;
+    e.set_auths(&[]);
+    let other_shares = manager_client
+        .mock_auths(&[MockAuth {
+            address: &keeper,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: &"execute_rebalance",
+                args: vec![&e, keeper.to_val(), plan_id_0.into_val(&e)],
+                sub_invokes: &[],
+            },
+        }])
+        .execute_rebalance(&keeper, &plan_id_0);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "execute_rebalance",
+        &keeper,
+        (plan_id_0, tokens, other_shares).into_val(&e),
+    );
+    assert!(other_shares > 0);
+    assert_eq!(
+        e.auths()[0],
+        (
+            keeper.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "execute_rebalance"),
+                    vec![&e, keeper.to_val(), plan_id_0.into_val(&e)]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    let backstop_bal = contracts
+        .backstop
+        .user_balance(&pool, &manager_client.address);
+    assert_eq!(backstop_bal.q4w.len(), 0);
+    assert_eq!(backstop_bal.shares, shares - amount_0);
+    let other_bal = contracts
+        .backstop
+        .user_balance(&other_pool, &manager_client.address);
+    assert_eq!(other_bal.shares, other_shares);
+    assert_eq!(contracts.backstop_token.balance(&manager_client.address), 0);
+    assert_eq!(manager_client.rebalances(&0, &10).len(), 0);
+    e.mock_all_auths();
+    let result = manager_client.try_execute_rebalance(&keeper, &plan_id_0);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(129))));
+}
+
+#[test]
+fn test_rebalance_reserved_shares() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let usdc = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let blnd = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let (contracts, pool) = create_blend_contracts(&e, &bombadil, &blnd.address(), &usdc.address());
+    let bootstrapper = create_backstop_bootstrapper(&e, &contracts);
+
+    let other_pool = contracts.pool_factory.deploy(
+        &bombadil,
+        &String::from_str(&e, "other"),
+        &BytesN::<32>::random(&e),
+        &Address::generate(&e),
+        &0,
+        &2,
+        &0,
+    );
+    let new_backstop = e.register(
+        backstop::WASM,
+        (
+            contracts.backstop_token.address.clone(),
+            contracts.emitter.address.clone(),
+            blnd.address(),
+            usdc.address(),
+            contracts.pool_factory.address.clone(),
+            Vec::<(Address, i128)>::new(&e),
+        ),
+    );
+
+    // start manager (samwise) at scope 2
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &samwise,
+        &2,
+        &bootstrapper.address,
+        &contracts.backstop_token.address,
+        &vec![&e, contracts.backstop.address.clone(), new_backstop.clone()],
+        &vec![&e, pool.clone(), other_pool.clone()],
+        &0,
+    );
+
+    let lp_mint_amount = 1_000_0000000;
+    contracts
+        .backstop_token
+        .transfer(&bombadil, &manager_client.address, &lp_mint_amount);
+    let shares =
+        manager_client.b_deposit(&frodo, &contracts.backstop.address, &pool, &lp_mint_amount);
+
+    /***** Plan queued before the manager's own shares *****/
+
+    let plan_amount = shares / 4;
+    let queue_amount = shares / 8;
+    let plan_id = manager_client.b_rebalance(
+        &samwise,
+        &contracts.backstop.address,
+        &pool,
+        &other_pool,
+        &plan_amount,
+    );
+    e.jump(ONE_DAY_LEDGERS);
+    manager_client.b_queue_withdrawal(&samwise, &contracts.backstop.address, &pool, &queue_amount);
+
+    // dequeue - validate only the manager's own shares can be dequeued
+    let result = manager_client.try_b_dequeue_withdrawal(
+        &samwise,
+        &contracts.backstop.address,
+        &pool,
+        &(queue_amount + 1),
+    );
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(132))));
+
+    // withdraw - validate the plan's unlocked shares can't be withdrawn or migrated
+    e.jump(16 * ONE_DAY_LEDGERS + 1);
+    let result = manager_client.try_b_withdraw(&samwise, &contracts.backstop.address, &pool, &1);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(132))));
+    let result = manager_client.try_b_migrate(
+        &samwise,
+        &contracts.backstop.address,
+        &new_backstop,
+        &pool,
+        &1,
+    );
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(132))));
+    let tokens_out =
+        manager_client.b_withdraw_unlocked(&samwise, &contracts.backstop.address, &pool);
+    assert_eq!(tokens_out, 0);
+
+    // execute - the plan's shares are still queued
+    let other_shares = manager_client.execute_rebalance(&samwise, &plan_id);
+    assert!(other_shares > 0);
+    let backstop_bal = contracts
+        .backstop
+        .user_balance(&pool, &manager_client.address);
+    assert_eq!(backstop_bal.q4w.len(), 1);
+    assert_eq!(backstop_bal.q4w.get(0).unwrap().amount, queue_amount);
+
+    // withdraw - the manager's own shares are released once they unlock
+    e.jump(ONE_DAY_LEDGERS);
+    let tokens_out =
+        manager_client.b_withdraw_unlocked(&samwise, &contracts.backstop.address, &pool);
+    assert!(tokens_out > 0);
+    let backstop_bal = contracts
+        .backstop
+        .user_balance(&pool, &manager_client.address);
+    assert_eq!(backstop_bal.q4w.len(), 0);
+    assert_eq!(backstop_bal.shares, shares - plan_amount - queue_amount);
+
+    /***** Manager's own shares queued before the plan *****/
+
+    manager_client.b_queue_withdrawal(&samwise, &contracts.backstop.address, &pool, &queue_amount);
+    e.jump(ONE_DAY_LEDGERS);
+    let plan_id = manager_client.b_rebalance(
+        &samwise,
+        &contracts.backstop.address,
+        &pool,
+        &other_pool,
+        &plan_amount,
+    );
+
+    // withdraw - validate the locked plan doesn't reserve the manager's own unlocked shares
+    e.jump(16 * ONE_DAY_LEDGERS + 1);
+    let result = manager_client.try_b_withdraw(
+        &samwise,
+        &contracts.backstop.address,
+        &pool,
+        &(queue_amount + 1),
+    );
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(132))));
+    let withdraw_amount = queue_amount / 2;
+    manager_client.b_withdraw(
+        &samwise,
+        &contracts.backstop.address,
+        &pool,
+        &withdraw_amount,
+    );
+    let tokens_out =
+        manager_client.b_withdraw_unlocked(&samwise, &contracts.backstop.address, &pool);
+    assert!(tokens_out > 0);
+    let record = manager_client.history(&0, &1).get(0).unwrap();
+    assert_eq!(
+        record.amounts,
+        vec![&e, queue_amount - withdraw_amount, tokens_out]
+    );
+    let backstop_bal = contracts
+        .backstop
+        .user_balance(&pool, &manager_client.address);
+    assert_eq!(backstop_bal.q4w.len(), 1);
+    assert_eq!(backstop_bal.q4w.get(0).unwrap().amount, plan_amount);
+
+    // cancel - dequeues only the plan's shares
+    manager_client.b_queue_withdrawal(&samwise, &contracts.backstop.address, &pool, &queue_amount);
+    manager_client.cancel_rebalance(&samwise, &plan_id);
+    let backstop_bal = contracts
+        .backstop
+        .user_balance(&pool, &manager_client.address);
+    assert_eq!(backstop_bal.q4w.len(), 1);
+    assert_eq!(backstop_bal.q4w.get(0).unwrap().amount, queue_amount);
+    let record = manager_client.history(&0, &1).get(0).unwrap();
+    assert_eq!(record.amounts, vec![&e, plan_id as i128, plan_amount]);
+    manager_client.b_dequeue_withdrawal(
+        &samwise,
+        &contracts.backstop.address,
+        &pool,
+        &queue_amount,
+    );
+
+    /***** Plan shares removed outside of the contract *****/
+
+    let plan_id = manager_client.b_rebalance(
+        &samwise,
+        &contracts.backstop.address,
+        &pool,
+        &other_pool,
+        &plan_amount,
+    );
+    e.as_contract(&manager_client.address, || {
+        BackstopClient::new(&e, &contracts.backstop.address).dequeue_withdrawal(
+            &manager_client.address,
+            &pool,
+            &plan_amount,
+        );
+    });
+
+    // cancel - drops the plan without dequeuing
+    manager_client.cancel_rebalance(&samwise, &plan_id);
+    let record = manager_client.history(&0, &1).get(0).unwrap();
+    assert_eq!(record.amounts, vec![&e, plan_id as i128, 0]);
+    assert_eq!(manager_client.rebalances(&0, &10).len(), 0);
+
+    // validate the reservation was released
+    manager_client.b_queue_withdrawal(&samwise, &contracts.backstop.address, &pool, &queue_amount);
+    manager_client.b_dequeue_withdrawal(
+        &samwise,
+        &contracts.backstop.address,
+        &pool,
+        &queue_amount,
+    );
+}
+
+#[test]
+fn test_migrate() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let usdc = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let blnd = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let (contracts, pool) = create_blend_contracts(&e, &bombadil, &blnd.address(), &usdc.address());
+    let bootstrapper = create_backstop_bootstrapper(&e, &contracts);
+
+    // deploy a new backstop with the same backstop token, and one with a different token
+    let new_backstop = e.register(
+        backstop::WASM,
+        (
+            contracts.backstop_token.address.clone(),
+            contracts.emitter.address.clone(),
+            blnd.address(),
+            usdc.address(),
+            contracts.pool_factory.address.clone(),
+            Vec::<(Address, i128)>::new(&e),
+        ),
+    );
+    let bad_backstop = e.register(
+        backstop::WASM,
+        (
+            blnd.address(),
+            contracts.emitter.address.clone(),
+            blnd.address(),
+            usdc.address(),
+            contracts.pool_factory.address.clone(),
+            Vec::<(Address, i128)>::new(&e),
+        ),
+    );
+
+    // start manager (samwise) at scope 2
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &samwise,
+        &2,
+        &bootstrapper.address,
+        &contracts.backstop_token.address,
+        &vec![
+            &e,
+            contracts.backstop.address.clone(),
+            new_backstop.clone(),
+            bad_backstop.clone(),
+        ],
+        &vec![&e, pool.clone()],
+        &0,
+    );
+
+    let lp_mint_amount = 1_000_0000000;
+    contracts
+        .backstop_token
+        .transfer(&bombadil, &manager_client.address, &lp_mint_amount);
+    let shares =
+        manager_client.b_deposit(&frodo, &contracts.backstop.address, &pool, &lp_mint_amount);
+    let amount = shares / 2;
+    manager_client.b_queue_withdrawal(&samwise, &contracts.backstop.address, &pool, &amount);
+    e.jump(17 * ONE_DAY_LEDGERS + 1);
+
+    // migrate - validate withdraw permission alone cannot migrate
+    manager_client.set_manager_permissions(&samwise, &B_WITHDRAW);
+    let result = manager_client.try_b_migrate(
+        &samwise,
+        &contracts.backstop.address,
+        &new_backstop,
+        &pool,
+        &amount,
+    );
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(4))));
+    manager_client.set_manager_permissions(&samwise, &SCOPE_HIGH);
+
+    // migrate - validates backstops
+    let result = manager_client.try_b_migrate(
+        &samwise,
+        &contracts.backstop.address,
+        &contracts.backstop.address,
+        &pool,
+        &amount,
+    );
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(101))));
+    let result = manager_client.try_b_migrate(
+        &samwise,
+        &contracts.backstop.address,
+        &Address::generate(&e),
+        &pool,
+        &amount,
+    );
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(101))));
+    let result = manager_client.try_b_migrate(
+        &samwise,
+        &contracts.backstop.address,
+        &bad_backstop,
+        &pool,
+        &amount,
+    );
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(131))));
+
+    // migrate - moves the unlocked shares to the new backstop
+    let new_shares = manager_client.b_migrate(
+        &samwise,
+        &contracts.backstop.address,
+        &new_backstop,
+        &pool,
+        &amount,
+    );
+    assert_eq!(
+        e.auths()[0],
+        (
+            samwise.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "b_migrate"),
+                    vec![
+                        &e,
+                        samwise.into_val(&e),
+                        contracts.backstop.address.into_val(&e),
+                        new_backstop.into_val(&e),
+                        pool.into_val(&e),
+                        amount.into_val(&e),
+                    ]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    // the new backstop pool is empty, so shares are minted 1:1 with tokens
+    let tokens = new_shares;
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "b_migrate",
+        &samwise,
+        (
+            contracts.backstop.address.clone(),
+            new_backstop.clone(),
+            pool.clone(),
+            amount,
+            tokens,
+            new_shares,
+        )
+            .into_val(&e),
+    );
+    let record = manager_client.history(&0, &1).get(0).unwrap();
+    assert_eq!(record.action, Symbol::new(&e, "b_migrate"));
+    assert_eq!(record.amounts, vec![&e, amount, tokens, new_shares]);
+    let old_bal = contracts
+        .backstop
+        .user_balance(&pool, &manager_client.address);
+    assert_eq!(old_bal.shares, shares - amount);
+    assert_eq!(old_bal.q4w.len(), 0);
+    let new_bal =
+        BackstopClient::new(&e, &new_backstop).user_balance(&pool, &manager_client.address);
+    assert_eq!(new_bal.shares, new_shares);
+    assert_eq!(contracts.backstop_token.balance(&manager_client.address), 0);
+}
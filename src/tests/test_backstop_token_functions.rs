@@ -1,14 +1,21 @@
 #![cfg(test)]
+#![allow(clippy::inconsistent_digit_grouping, clippy::needless_borrow)]
 
 use soroban_sdk::{
-    testutils::{Address as _, EnvTestConfig, MockAuth, MockAuthInvoke},
+    testutils::{
+        Address as _, AuthorizedFunction, AuthorizedInvocation, EnvTestConfig, MockAuth,
+        MockAuthInvoke,
+    },
     token::{StellarAssetClient, TokenClient},
-    vec, Address, Env, Error, IntoVal, Vec,
+    vec, Address, Env, Error, IntoVal, Symbol, Vec,
 };
 
+use crate::permissions::SCOPE_HIGH;
 use crate::testutils::{
+    assert_last_event,
+    contract::{LimitKey, SlippageFloor},
     create_backstop_bootstrapper, create_backstop_manager_wasm, create_blend_contracts,
-    EnvTestUtils,
+    EnvTestUtils, ONE_DAY_LEDGERS,
 };
 
 #[test]
@@ -41,6 +48,7 @@ fn test_execute_comet_functions() {
         &contracts.backstop_token.address,
         &vec![&e, contracts.backstop.address.clone()],
         &vec![&e, pool.clone()],
+        &0,
     );
 
     // mint underlying tokens to the backstop manager contract
@@ -78,6 +86,13 @@ fn test_execute_comet_functions() {
         }])
         .c_join_pool(&frodo, &lp_mint_amount, &max_amount_in);
     assert_eq!(e.auths()[0].0, frodo); // assert require_auth exists
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "c_join_pool",
+        &frodo,
+        (lp_mint_amount, max_amount_in.clone()).into_val(&e),
+    );
     let blnd_balance_1 = blnd_token.balance(&manager_client.address);
     let usdc_balance_1 = usdc_token.balance(&manager_client.address);
     let lp_balance_1 = contracts.backstop_token.balance(&manager_client.address);
@@ -144,6 +159,13 @@ fn test_execute_comet_functions() {
         }])
         .c_exit_pool(&frodo, &lp_mint_amount, &min_amount_out);
     assert_eq!(e.auths()[0].0, frodo); // assert require_auth exists
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "c_exit_pool",
+        &frodo,
+        (lp_mint_amount, min_amount_out.clone()).into_val(&e),
+    );
     let blnd_balance_2 = blnd_token.balance(&manager_client.address);
     let usdc_balance_2 = usdc_token.balance(&manager_client.address);
     let lp_balance_2 = contracts.backstop_token.balance(&manager_client.address);
@@ -160,16 +182,18 @@ fn test_execute_comet_functions() {
             address: &frodo,
             invoke: &MockAuthInvoke {
                 contract: &manager_client.address,
-                fn_name: &"set_manager",
+                fn_name: &"update_manager",
                 args: vec![&e, samwise.into_val(&e), 2u32.into_val(&e)],
                 sub_invokes: &[],
             },
         }])
-        .set_manager(&samwise, &2u32);
+        .update_manager(&samwise, &2u32);
 
-    let manager = manager_client.manager();
-    assert_eq!(manager.id, samwise);
-    assert_eq!(manager.scope, 2);
+    let managers = manager_client.managers();
+    assert_eq!(
+        managers.get(samwise.clone()).unwrap().permissions,
+        SCOPE_HIGH
+    );
 
     // join pool - as manager
     e.set_auths(&[]);
@@ -190,6 +214,13 @@ fn test_execute_comet_functions() {
         }])
         .c_join_pool(&samwise, &lp_mint_amount, &max_amount_in);
     assert_eq!(e.auths()[0].0, samwise); // assert require_auth exists
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "c_join_pool",
+        &samwise,
+        (lp_mint_amount, max_amount_in.clone()).into_val(&e),
+    );
     let blnd_balance_3 = blnd_token.balance(&manager_client.address);
     let usdc_balance_3 = usdc_token.balance(&manager_client.address);
     let lp_balance_3 = contracts.backstop_token.balance(&manager_client.address);
@@ -216,6 +247,13 @@ fn test_execute_comet_functions() {
         }])
         .c_exit_pool(&samwise, &lp_mint_amount, &min_amount_out);
     assert_eq!(e.auths()[0].0, samwise); // assert require_auth exists
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "c_exit_pool",
+        &samwise,
+        (lp_mint_amount, min_amount_out.clone()).into_val(&e),
+    );
     let blnd_balance_4 = blnd_token.balance(&manager_client.address);
     let usdc_balance_4 = usdc_token.balance(&manager_client.address);
     let lp_balance_4 = contracts.backstop_token.balance(&manager_client.address);
@@ -223,3 +261,475 @@ fn test_execute_comet_functions() {
     assert!(usdc_balance_4 >= usdc_balance_3 + min_amount_out.get_unchecked(1));
     assert_eq!(lp_balance_4, 0);
 }
+
+#[test]
+fn test_exit_limit() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let usdc = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let blnd = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let (contracts, pool) = create_blend_contracts(&e, &bombadil, &blnd.address(), &usdc.address());
+    let bootstrapper = create_backstop_bootstrapper(&e, &contracts);
+
+    // start manager (samwise) at scope 2
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &samwise,
+        &2,
+        &bootstrapper.address,
+        &contracts.backstop_token.address,
+        &vec![&e, contracts.backstop.address.clone()],
+        &vec![&e, pool.clone()],
+        &0,
+    );
+
+    // bombadil sent LP tokens to the backstop manager contract
+    let lp_balance_0 = 100_0000000;
+    contracts
+        .backstop_token
+        .transfer(&bombadil, &manager_client.address, &lp_balance_0);
+
+    // limit managers to exiting 40 LP tokens per day
+    let max_amount: i128 = 40_0000000;
+    manager_client.set_limit(&LimitKey::Exit, &max_amount, &ONE_DAY_LEDGERS);
+
+    // exit pool - validate manager cannot exceed the limit
+    let min_amount_out: Vec<i128> = vec![&e, 0, 0];
+    let result = manager_client.try_c_exit_pool(&samwise, &(max_amount + 1), &min_amount_out);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(113))));
+
+    // exit pool - as manager up to the limit
+    manager_client.c_exit_pool(&samwise, &max_amount, &min_amount_out);
+    let lp_balance_1 = contracts.backstop_token.balance(&manager_client.address);
+    assert_eq!(lp_balance_1, lp_balance_0 - max_amount);
+    assert_eq!(manager_client.remaining_limit(&LimitKey::Exit), Some(0));
+
+    // exit pool - as owner is not limited
+    manager_client.c_exit_pool(&frodo, &(max_amount + 1), &min_amount_out);
+    let lp_balance_2 = contracts.backstop_token.balance(&manager_client.address);
+    assert_eq!(lp_balance_2, lp_balance_1 - max_amount - 1);
+
+    // exit pool - as manager after the period resets
+    e.jump(ONE_DAY_LEDGERS);
+    assert_eq!(
+        manager_client.remaining_limit(&LimitKey::Exit),
+        Some(max_amount)
+    );
+    manager_client.c_exit_pool(&samwise, &lp_balance_2, &min_amount_out);
+    assert_eq!(contracts.backstop_token.balance(&manager_client.address), 0);
+}
+
+#[test]
+fn test_join_single() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let usdc = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let blnd = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let usdc_admin_client = StellarAssetClient::new(&e, &usdc.address());
+    let blnd_admin_client = StellarAssetClient::new(&e, &blnd.address());
+    let (contracts, pool) = create_blend_contracts(&e, &bombadil, &blnd.address(), &usdc.address());
+    let bootstrapper = create_backstop_bootstrapper(&e, &contracts);
+
+    // start manager (samwise) at scope 1
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &samwise,
+        &1,
+        &bootstrapper.address,
+        &contracts.backstop_token.address,
+        &vec![&e, contracts.backstop.address.clone()],
+        &vec![&e, pool.clone()],
+        &0,
+    );
+    let blnd_balance_0 = 100_000_0000000;
+    let usdc_balance_0 = 2500_0000000;
+    blnd_admin_client.mint(&manager_client.address, &blnd_balance_0);
+    usdc_admin_client.mint(&manager_client.address, &usdc_balance_0);
+    let blnd_token = TokenClient::new(&e, &blnd.address());
+    let usdc_token = TokenClient::new(&e, &usdc.address());
+
+    // join single - validate requires scope 2 as manager
+    let token_amount_in = 1000_0000000;
+    e.set_auths(&[]);
+    let result = manager_client
+        .mock_auths(&[MockAuth {
+            address: &samwise,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: &"c_join_single",
+                args: vec![
+                    &e,
+                    samwise.into_val(&e),
+                    0u32.into_val(&e),
+                    token_amount_in.into_val(&e),
+                    0i128.into_val(&e),
+                ],
+                sub_invokes: &[],
+            },
+        }])
+        .try_c_join_single(&samwise, &0, &token_amount_in, &0);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(4))));
+    e.mock_all_auths();
+    manager_client.set_manager_permissions(&samwise, &SCOPE_HIGH);
+
+    // join single - validates token index
+    let result = manager_client.try_c_join_single(&samwise, &2, &token_amount_in, &0);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(103))));
+
+    // join proportionally for 100 shares
+    let lp_mint_amount = 100_0000000;
+    let max_amount_in: Vec<i128> = vec![&e, 1001_0000000, 25_1000000];
+    manager_client.c_join_pool(&samwise, &lp_mint_amount, &max_amount_in);
+    let blnd_balance_1 = blnd_token.balance(&manager_client.address);
+    let usdc_balance_1 = usdc_token.balance(&manager_client.address);
+    let blnd_in = blnd_balance_0 - blnd_balance_1;
+    assert_eq!(
+        contracts.backstop_token.balance(&manager_client.address),
+        lp_mint_amount
+    );
+
+    // join single - validates the minimum pool amount out
+    let result = manager_client.try_c_join_single(&samwise, &0, &blnd_in, &lp_mint_amount);
+    assert!(result.is_err());
+
+    // join single - deposit the same amount of BLND as the proportional join, without USDC
+    e.set_auths(&[]);
+    let pool_amount_out = manager_client
+        .mock_auths(&[MockAuth {
+            address: &samwise,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: &"c_join_single",
+                args: vec![
+                    &e,
+                    samwise.into_val(&e),
+                    0u32.into_val(&e),
+                    blnd_in.into_val(&e),
+                    0i128.into_val(&e),
+                ],
+                sub_invokes: &[],
+            },
+        }])
+        .c_join_single(&samwise, &0, &blnd_in, &0);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "c_join_single",
+        &samwise,
+        (0u32, blnd_in, pool_amount_out).into_val(&e),
+    );
+    // the comet pool's pull of the BLND is pre-authorized by the contract's approval, so only
+    // the manager signs the call
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            samwise.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "c_join_single"),
+                    vec![
+                        &e,
+                        samwise.into_val(&e),
+                        0u32.into_val(&e),
+                        blnd_in.into_val(&e),
+                        0i128.into_val(&e),
+                    ]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        blnd_token.balance(&manager_client.address),
+        blnd_balance_1 - blnd_in
+    );
+    assert_eq!(usdc_token.balance(&manager_client.address), usdc_balance_1);
+    assert_eq!(
+        contracts.backstop_token.balance(&manager_client.address),
+        lp_mint_amount + pool_amount_out
+    );
+    // BLND is 80% of the pool's weight, so the single sided join mints slightly less than 80%
+    // of the shares of the proportional join after swap fees
+    assert!(pool_amount_out < lp_mint_amount * 80 / 100);
+    assert!(pool_amount_out > lp_mint_amount * 79 / 100);
+    let record = manager_client.history(&0, &1).get(0).unwrap();
+    assert_eq!(record.amounts, vec![&e, blnd_in, pool_amount_out]);
+}
+
+#[test]
+fn test_exit_single() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let usdc = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let blnd = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let (contracts, pool) = create_blend_contracts(&e, &bombadil, &blnd.address(), &usdc.address());
+    let bootstrapper = create_backstop_bootstrapper(&e, &contracts);
+
+    // start manager (samwise) at scope 1
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &samwise,
+        &1,
+        &bootstrapper.address,
+        &contracts.backstop_token.address,
+        &vec![&e, contracts.backstop.address.clone()],
+        &vec![&e, pool.clone()],
+        &0,
+    );
+    let usdc_token = TokenClient::new(&e, &usdc.address());
+
+    // bombadil sent LP tokens to the backstop manager contract
+    let lp_balance_0 = 100_0000000;
+    contracts
+        .backstop_token
+        .transfer(&bombadil, &manager_client.address, &lp_balance_0);
+
+    // exit single - validate requires scope 2 as manager
+    let burn_amount = 10_0000000;
+    e.set_auths(&[]);
+    let result = manager_client
+        .mock_auths(&[MockAuth {
+            address: &samwise,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: &"c_exit_single",
+                args: vec![
+                    &e,
+                    samwise.into_val(&e),
+                    1u32.into_val(&e),
+                    burn_amount.into_val(&e),
+                    0i128.into_val(&e),
+                ],
+                sub_invokes: &[],
+            },
+        }])
+        .try_c_exit_single(&samwise, &1, &burn_amount, &0);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(4))));
+    e.mock_all_auths();
+    manager_client.set_manager_permissions(&samwise, &SCOPE_HIGH);
+
+    // exit single - validates token index
+    let result = manager_client.try_c_exit_single(&samwise, &2, &burn_amount, &0);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(103))));
+
+    // exit proportionally for 10 shares
+    let usdc_balance_0 = usdc_token.balance(&manager_client.address);
+    manager_client.c_exit_pool(&samwise, &burn_amount, &vec![&e, 0, 0]);
+    let usdc_balance_1 = usdc_token.balance(&manager_client.address);
+    let usdc_out = usdc_balance_1 - usdc_balance_0;
+
+    // exit single - validates the minimum token out
+    let result = manager_client.try_c_exit_single(&samwise, &1, &burn_amount, &(usdc_out * 5));
+    assert!(result.is_err());
+
+    // exit single - burn the same amount of shares as the proportional exit for only USDC
+    e.set_auths(&[]);
+    let token_amount_out = manager_client
+        .mock_auths(&[MockAuth {
+            address: &samwise,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: &"c_exit_single",
+                args: vec![
+                    &e,
+                    samwise.into_val(&e),
+                    1u32.into_val(&e),
+                    burn_amount.into_val(&e),
+                    usdc_out.into_val(&e),
+                ],
+                sub_invokes: &[],
+            },
+        }])
+        .c_exit_single(&samwise, &1, &burn_amount, &usdc_out);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "c_exit_single",
+        &samwise,
+        (1u32, burn_amount, token_amount_out).into_val(&e),
+    );
+    // the contract exits the comet pool as the direct invoker, so only the manager signs the
+    // call
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            samwise.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "c_exit_single"),
+                    vec![
+                        &e,
+                        samwise.into_val(&e),
+                        1u32.into_val(&e),
+                        burn_amount.into_val(&e),
+                        usdc_out.into_val(&e),
+                    ]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        usdc_token.balance(&manager_client.address),
+        usdc_balance_1 + token_amount_out
+    );
+    assert_eq!(
+        contracts.backstop_token.balance(&manager_client.address),
+        lp_balance_0 - 2 * burn_amount
+    );
+    // USDC is 20% of the pool's weight, so the single sided exit returns slightly less than 5x
+    // the USDC of the proportional exit after swap fees
+    assert!(token_amount_out < usdc_out * 5);
+    assert!(token_amount_out > usdc_out * 49 / 10);
+    let record = manager_client.history(&0, &1).get(0).unwrap();
+    assert_eq!(record.amounts, vec![&e, burn_amount, token_amount_out]);
+
+    // exit single - validate manager cannot exceed the exit limit
+    e.mock_all_auths();
+    let max_amount: i128 = 40_0000000;
+    manager_client.set_limit(&LimitKey::Exit, &max_amount, &ONE_DAY_LEDGERS);
+    let result = manager_client.try_c_exit_single(&samwise, &1, &(max_amount + 1), &0);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(113))));
+    manager_client.c_exit_single(&samwise, &1, &max_amount, &0);
+    assert_eq!(manager_client.remaining_limit(&LimitKey::Exit), Some(0));
+}
+
+#[test]
+fn test_slippage_floor() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let usdc = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let blnd = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let blnd_admin_client = StellarAssetClient::new(&e, &blnd.address());
+    let (contracts, pool) = create_blend_contracts(&e, &bombadil, &blnd.address(), &usdc.address());
+    let bootstrapper = create_backstop_bootstrapper(&e, &contracts);
+
+    // start manager (samwise) at scope 2, with a timelock
+    let timelock = ONE_DAY_LEDGERS;
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &samwise,
+        &2,
+        &bootstrapper.address,
+        &contracts.backstop_token.address,
+        &vec![&e, contracts.backstop.address.clone()],
+        &vec![&e, pool.clone()],
+        &timelock,
+    );
+    blnd_admin_client.mint(&manager_client.address, &10_000_0000000);
+    contracts
+        .backstop_token
+        .transfer(&bombadil, &manager_client.address, &100_0000000);
+
+    // measure the rates of single sided joins and exits without a floor
+    let blnd_in = 100_0000000;
+    let burn_amount = 1_0000000;
+    assert!(manager_client.slippage_floor(&blnd.address()).is_none());
+    let lp_out = manager_client.c_join_single(&samwise, &0, &blnd_in, &0);
+    let usdc_out = manager_client.c_exit_single(&samwise, &1, &burn_amount, &0);
+    let lp_out_ratio = lp_out * 1_0000000 / blnd_in;
+    let usdc_out_ratio = usdc_out * 1_0000000 / burn_amount;
+
+    // set slippage floor - validates the token and ratios
+    let mut floor = SlippageFloor {
+        min_lp_out_ratio: lp_out_ratio * 2,
+        min_token_out_ratio: 0,
+    };
+    let result = manager_client.try_set_slippage_floor(&Address::generate(&e), &floor);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(103))));
+    floor.min_token_out_ratio = -1;
+    let result = manager_client.try_set_slippage_floor(&blnd.address(), &floor);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(133))));
+    floor.min_token_out_ratio = 1_000_000_0000001;
+    let result = manager_client.try_set_slippage_floor(&blnd.address(), &floor);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(133))));
+    floor.min_token_out_ratio = 0;
+
+    // set slippage floor - adding a floor applies immediately
+    manager_client.set_slippage_floor(&blnd.address(), &floor);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "set_slippage_floor",
+        &frodo,
+        (blnd.address(), floor.clone()).into_val(&e),
+    );
+    assert_eq!(e.auths()[0].0, frodo);
+    let usdc_floor = SlippageFloor {
+        min_lp_out_ratio: 0,
+        min_token_out_ratio: usdc_out_ratio * 2,
+    };
+    manager_client.set_slippage_floor(&usdc.address(), &usdc_floor);
+    assert_eq!(manager_client.pending_changes().len(), 0);
+
+    // join and exit single - validate the floor applies even if the manager passes 0
+    let result = manager_client.try_c_join_single(&samwise, &0, &blnd_in, &0);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(125))));
+    let result = manager_client.try_c_exit_single(&samwise, &1, &burn_amount, &0);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(125))));
+
+    // set slippage floor - lowering a floor is timelocked
+    floor.min_lp_out_ratio = lp_out_ratio / 2;
+    manager_client.set_slippage_floor(&blnd.address(), &floor);
+    assert_eq!(manager_client.pending_changes().len(), 1);
+    assert_eq!(
+        manager_client
+            .slippage_floor(&blnd.address())
+            .unwrap()
+            .min_lp_out_ratio,
+        lp_out_ratio * 2
+    );
+    let result = manager_client.try_c_join_single(&samwise, &0, &blnd_in, &0);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(125))));
+    e.jump(timelock);
+    manager_client.execute_pending(&0);
+    assert_eq!(
+        manager_client
+            .slippage_floor(&blnd.address())
+            .unwrap()
+            .min_lp_out_ratio,
+        lp_out_ratio / 2
+    );
+
+    // join single - succeeds above the floor
+    let lp_out = manager_client.c_join_single(&samwise, &0, &blnd_in, &0);
+    assert!(lp_out * 1_0000000 >= blnd_in * floor.min_lp_out_ratio);
+}
@@ -1,8 +1,14 @@
 #![cfg(test)]
+#![allow(
+    clippy::inconsistent_digit_grouping,
+    clippy::needless_borrow,
+    clippy::unnecessary_cast
+)]
 
+use crate::permissions::{SCOPE_HIGH, SCOPE_LOW};
 use crate::testutils::{
-    create_backstop_bootstrapper, create_backstop_manager_wasm, create_blend_contracts,
-    EnvTestUtils,
+    assert_last_event, contract::BootstrapOutcome, create_backstop_bootstrapper,
+    create_backstop_manager_wasm, create_blend_contracts, EnvTestUtils,
 };
 use soroban_sdk::{
     testutils::{Address as _, EnvTestConfig, MockAuth, MockAuthInvoke},
@@ -41,6 +47,7 @@ fn test_execute_bootstrapper_functions() {
         &contracts.backstop_token.address,
         &vec![&e, contracts.backstop.address.clone()],
         &vec![&e, pool.clone()],
+        &0,
     );
 
     // mint underlying tokens to the backstop manager contract
@@ -99,17 +106,19 @@ fn test_execute_bootstrapper_functions() {
             address: &frodo,
             invoke: &MockAuthInvoke {
                 contract: &manager_client.address,
-                fn_name: &"set_manager",
+                fn_name: &"update_manager",
                 args: vec![&e, samwise.into_val(&e), 2u32.into_val(&e)],
                 sub_invokes: &[],
             },
         }])
-        .set_manager(&samwise, &2u32);
+        .update_manager(&samwise, &2u32);
 
     // assert scope is 2
-    let manager = manager_client.manager();
-    assert_eq!(2, manager.scope);
-    assert_eq!(samwise, manager.id);
+    let managers = manager_client.managers();
+    assert_eq!(
+        SCOPE_HIGH,
+        managers.get(samwise.clone()).unwrap().permissions
+    );
 
     // create_bootstrap - as manager
     e.set_auths(&[]);
@@ -140,6 +149,13 @@ fn test_execute_bootstrapper_functions() {
             &pool,
         );
     assert_eq!(e.auths()[0].0, samwise); // assert require_auth exists
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "bb_start_bootstrap",
+        &samwise,
+        (id, blnd_index, blnd_bootstrap_amount, pool.clone()).into_val(&e),
+    );
     assert_eq!(id, 0);
     let blnd_balance_1 = blnd_token.balance(&manager_client.address);
     assert_eq!(blnd_balance_1, blnd_balance_0 - blnd_bootstrap_amount);
@@ -149,6 +165,18 @@ fn test_execute_bootstrapper_functions() {
     );
     let bootstrap = bootstrapper.get_bootstrap(&0);
     assert_eq!(bootstrap.data.bootstrap_amount, blnd_bootstrap_amount);
+    let bootstraps = manager_client.bootstraps(&0, &10);
+    assert_eq!(bootstraps.len(), 1);
+    let info = bootstraps.get(0).unwrap();
+    assert_eq!(info.record.id, id);
+    assert_eq!(info.record.pool, pool);
+    assert_eq!(info.record.token_index, blnd_index);
+    assert_eq!(info.record.amount, blnd_bootstrap_amount);
+    assert_eq!(info.record.pair_min, pair_min_usdc);
+    assert_eq!(info.record.close_ledger, bootstrap.config.close_ledger);
+    assert_eq!(info.record.outcome, BootstrapOutcome::Open);
+    assert_eq!(info.status, 0);
+    assert_eq!(info.total_pair, 0);
 
     // frodo join bootstrap
     e.mock_all_auths();
@@ -175,17 +203,19 @@ fn test_execute_bootstrapper_functions() {
             address: &frodo,
             invoke: &MockAuthInvoke {
                 contract: &manager_client.address,
-                fn_name: &"set_manager",
+                fn_name: &"update_manager",
                 args: vec![&e, samwise.into_val(&e), 0u32.into_val(&e)],
                 sub_invokes: &[],
             },
         }])
-        .set_manager(&samwise, &0u32);
+        .update_manager(&samwise, &0u32);
 
     // assert scope is 0
-    let manager = manager_client.manager();
-    assert_eq!(0, manager.scope);
-    assert_eq!(samwise, manager.id);
+    let managers = manager_client.managers();
+    assert_eq!(
+        SCOPE_LOW,
+        managers.get(samwise.clone()).unwrap().permissions
+    );
 
     // claim bootstrap - as manager
     e.set_auths(&[]);
@@ -206,12 +236,32 @@ fn test_execute_bootstrapper_functions() {
         }])
         .bb_claim_bootstrap(&samwise, &bootstrap.id, &contracts.backstop.address);
     assert_eq!(e.auths()[0].0, samwise); // assert require_auth exists
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "bb_claim_bootstrap",
+        &samwise,
+        (
+            bootstrap.id,
+            contracts.backstop.address.clone(),
+            actual_claim_amount,
+        )
+            .into_val(&e),
+    );
     let backstop_balance_1 = contracts
         .backstop
         .user_balance(&pool, &manager_client.address);
     // shares are 1-1 with backstop tokens
     assert_eq!(actual_claim_amount, claim_amount);
     assert_eq!(backstop_balance_1.shares, claim_amount);
+    let info = manager_client.bootstraps(&0, &10).get(0).unwrap();
+    assert_eq!(info.record.outcome, BootstrapOutcome::Claimed);
+    assert_eq!(info.status, 2);
+    assert_eq!(info.total_pair, usdc_balance_0);
+    assert_eq!(
+        info.total_backstop_tokens,
+        bootstrap.data.total_backstop_tokens
+    );
 }
 
 #[test]
@@ -245,6 +295,7 @@ fn test_execute_bootstrapper_functions_cancelled() {
         &contracts.backstop_token.address,
         &vec![&e, contracts.backstop.address.clone()],
         &vec![&e, pool.clone()],
+        &0,
     );
 
     // mint underlying tokens to the backstop manager contract
@@ -263,7 +314,7 @@ fn test_execute_bootstrapper_functions_cancelled() {
     // create_bootstrap - as owner
     let blnd_bootstrap_amount = blnd_balance_0;
     e.set_auths(&[]);
-    manager_client
+    let id = manager_client
         .mock_auths(&[MockAuth {
             address: &frodo,
             invoke: &MockAuthInvoke {
@@ -290,6 +341,13 @@ fn test_execute_bootstrapper_functions_cancelled() {
             &pool,
         );
     assert_eq!(e.auths()[0].0, frodo); // assert require_auth exists
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "bb_start_bootstrap",
+        &frodo,
+        (id, blnd_index, blnd_bootstrap_amount, pool.clone()).into_val(&e),
+    );
     let blnd_balance_1 = blnd_token.balance(&manager_client.address);
     assert_eq!(blnd_balance_1, blnd_balance_0 - blnd_bootstrap_amount);
     assert_eq!(
@@ -303,9 +361,11 @@ fn test_execute_bootstrapper_functions_cancelled() {
     e.jump(duration + 1);
 
     // assert scope is 0
-    let manager = manager_client.manager();
-    assert_eq!(0, manager.scope);
-    assert_eq!(samwise, manager.id);
+    let managers = manager_client.managers();
+    assert_eq!(
+        SCOPE_LOW,
+        managers.get(samwise.clone()).unwrap().permissions
+    );
 
     // refund bootstrap - as manager
     e.set_auths(&[]);
@@ -322,6 +382,102 @@ fn test_execute_bootstrapper_functions_cancelled() {
         }])
         .bb_refund_bootstrap(&samwise, &bootstrap_id);
     assert_eq!(e.auths()[0].0, samwise); // assert require_auth exists
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "bb_refund_bootstrap",
+        &samwise,
+        (bootstrap_id, refund_amount).into_val(&e),
+    );
     assert_eq!(refund_amount, blnd_bootstrap_amount);
-    assert_eq!(blnd_token.balance(&manager_client.address), blnd_balance_0)
+    assert_eq!(blnd_token.balance(&manager_client.address), blnd_balance_0);
+    let bootstraps = manager_client.bootstraps(&0, &10);
+    assert_eq!(bootstraps.len(), 1);
+    let info = bootstraps.get(0).unwrap();
+    assert_eq!(info.record.id, bootstrap_id);
+    assert_eq!(info.record.outcome, BootstrapOutcome::Refunded);
+    assert_eq!(info.status, 3);
+    assert_eq!(manager_client.bootstraps(&1, &10).len(), 0);
+}
+
+#[test]
+fn test_bootstrapper_functions_invalid_addresses() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let usdc = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let blnd = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let blnd_admin_client = StellarAssetClient::new(&e, &blnd.address());
+    let (contracts, pool) = create_blend_contracts(&e, &bombadil, &blnd.address(), &usdc.address());
+    let bootstrapper = create_backstop_bootstrapper(&e, &contracts);
+    let blnd_index: u32 = 0;
+    let invalid_pool = Address::generate(&e);
+    let invalid_backstop = Address::generate(&e);
+
+    // start manager (samwise) at scope 2
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &samwise,
+        &2,
+        &bootstrapper.address,
+        &contracts.backstop_token.address,
+        &vec![&e, contracts.backstop.address.clone()],
+        &vec![&e, pool.clone()],
+        &0,
+    );
+
+    let blnd_balance_0 = 100_000_0000000;
+    blnd_admin_client.mint(&manager_client.address, &blnd_balance_0);
+    let blnd_token = TokenClient::new(&e, &blnd_admin_client.address);
+    let duration: u32 = 17280 + 1;
+    let pair_min_usdc: i128 = 10_000_0000;
+
+    // create_bootstrap - validate pool must be in the valid list
+    let result = manager_client.try_bb_start_bootstrap(
+        &samwise,
+        &blnd_index,
+        &blnd_balance_0,
+        &pair_min_usdc,
+        &duration,
+        &invalid_pool,
+    );
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(101))));
+
+    // create_bootstrap - validate the owner is also restricted to the valid list
+    let result = manager_client.try_bb_start_bootstrap(
+        &frodo,
+        &blnd_index,
+        &blnd_balance_0,
+        &pair_min_usdc,
+        &duration,
+        &invalid_pool,
+    );
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(101))));
+    assert_eq!(blnd_token.balance(&manager_client.address), blnd_balance_0);
+
+    // create_bootstrap - with a valid pool
+    let id = manager_client.bb_start_bootstrap(
+        &samwise,
+        &blnd_index,
+        &blnd_balance_0,
+        &pair_min_usdc,
+        &duration,
+        &pool,
+    );
+    assert_eq!(id, 0);
+
+    // claim_bootstrap - validate backstop must be in the valid list
+    e.jump(duration + 1);
+    let result = manager_client.try_bb_claim_bootstrap(&samwise, &id, &invalid_backstop);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(101))));
+    let result = manager_client.try_bb_claim_bootstrap(&frodo, &id, &invalid_backstop);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(101))));
 }
@@ -1,4 +1,5 @@
 #![cfg(test)]
+#![allow(clippy::inconsistent_digit_grouping, clippy::needless_borrow)]
 
 use soroban_sdk::{
     testutils::{
@@ -6,12 +7,18 @@ use soroban_sdk::{
         MockAuthInvoke,
     },
     token::{StellarAssetClient, TokenClient},
-    vec, Address, Env, Error, IntoVal, Symbol,
+    vec, Address, Env, Error, IntoVal, Symbol, Vec,
 };
 
+use crate::permissions::{
+    ALL, B_CLAIM, B_WITHDRAW, REFUND_TOKEN, SCOPE_HIGH, SCOPE_LOW, SCOPE_MEDIUM,
+};
+use crate::storage::Manager;
 use crate::testutils::{
+    assert_last_event, contract,
+    contract::{ConfigChange, LimitKey, Pair, PendingChange},
     create_backstop_bootstrapper, create_backstop_manager_wasm, create_blend_contracts,
-    EnvTestUtils,
+    EnvTestUtils, ONE_DAY_LEDGERS,
 };
 
 #[test]
@@ -44,6 +51,7 @@ fn test_execute_comet_functions() {
         &contracts.backstop_token.address,
         &vec![&e, contracts.backstop.address.clone()],
         &vec![&e, pool.clone()],
+        &0,
     );
 
     // mint tokens to the backstop manager contract
@@ -57,18 +65,76 @@ fn test_execute_comet_functions() {
 
     // validate the initial state of the backstop manager
     assert_eq!(manager_client.owner(), frodo);
-    let manager = manager_client.manager();
-    assert_eq!(manager.id, samwise);
-    assert_eq!(manager.scope, 2);
+    let managers = manager_client.managers();
+    assert_eq!(managers.len(), 1);
+    assert_eq!(
+        managers.get(samwise.clone()).unwrap().permissions,
+        SCOPE_HIGH
+    );
+    assert_eq!(
+        managers.get(samwise.clone()).unwrap().expires_at_ledger,
+        None
+    );
     assert_eq!(manager_client.backstop_bootstrapper(), bootstrapper.address);
     assert_eq!(
-        manager_client.backstops(),
+        manager_client.backstops(&0, &10),
         vec![&e, contracts.backstop.address.clone()]
     );
-    assert_eq!(manager_client.pools(), vec![&e, pool.clone()]);
+    assert_eq!(manager_client.pools(&0, &10), vec![&e, pool.clone()]);
+
+    // add manager
+    manager_client.add_manager(&merry, &0u32, &None);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "add_manager",
+        &frodo,
+        (merry.clone(), SCOPE_LOW, None::<u32>).into_val(&e),
+    );
+    assert_eq!(
+        e.auths()[0],
+        (
+            frodo.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "add_manager"),
+                    vec![
+                        &e,
+                        merry.to_val(),
+                        0u32.into_val(&e),
+                        Option::<u32>::None.into_val(&e)
+                    ]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    let managers = manager_client.managers();
+    assert_eq!(managers.len(), 2);
+    assert_eq!(
+        managers.get(samwise.clone()).unwrap().permissions,
+        SCOPE_HIGH
+    );
+    assert_eq!(managers.get(merry.clone()).unwrap().permissions, SCOPE_LOW);
+
+    // add manager - validate existing managers cannot be re-added
+    let result = manager_client.try_add_manager(&merry, &1u32, &None);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(107))));
+
+    // add manager - validate scope > 2 errors
+    let result = manager_client.try_add_manager(&Address::generate(&e), &3u32, &None);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(102))));
 
     // update manager
-    manager_client.set_manager(&merry, &0u32);
+    manager_client.update_manager(&samwise, &1u32);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "update_manager",
+        &frodo,
+        (samwise.clone(), SCOPE_HIGH, SCOPE_MEDIUM).into_val(&e),
+    );
     assert_eq!(
         e.auths()[0],
         (
@@ -76,24 +142,77 @@ fn test_execute_comet_functions() {
             AuthorizedInvocation {
                 function: AuthorizedFunction::Contract((
                     manager_client.address.clone(),
-                    Symbol::new(&e, "set_manager"),
-                    vec![&e, merry.to_val(), 0u32.into_val(&e),]
+                    Symbol::new(&e, "update_manager"),
+                    vec![&e, samwise.to_val(), 1u32.into_val(&e),]
                 )),
                 sub_invocations: std::vec![]
             }
         )
     );
-    let manager = manager_client.manager();
-    assert_eq!(manager.id, merry);
-    assert_eq!(manager.scope, 0);
+    let managers = manager_client.managers();
+    assert_eq!(
+        managers.get(samwise.clone()).unwrap().permissions,
+        SCOPE_MEDIUM
+    );
+    assert_eq!(managers.get(merry.clone()).unwrap().permissions, SCOPE_LOW);
 
     // update manager - validate scope > 2 errors
-    let result = manager_client.try_set_manager(&merry, &3u32);
+    let result = manager_client.try_update_manager(&merry, &3u32);
     assert_eq!(result.err(), Some(Ok(Error::from_contract_error(102))));
 
+    // update manager - validate manager must exist
+    let result = manager_client.try_update_manager(&Address::generate(&e), &1u32);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(108))));
+
+    // remove manager
+    manager_client.remove_manager(&samwise);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "remove_manager",
+        &frodo,
+        samwise.clone().into_val(&e),
+    );
+    assert_eq!(
+        e.auths()[0],
+        (
+            frodo.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "remove_manager"),
+                    vec![&e, samwise.to_val(),]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    let managers = manager_client.managers();
+    assert_eq!(managers.len(), 1);
+    assert_eq!(managers.get(merry.clone()).unwrap().permissions, SCOPE_LOW);
+
+    // remove manager - validate manager must exist
+    let result = manager_client.try_remove_manager(&samwise);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(108))));
+
+    // add manager - validate max managers error
+    for _ in 0..9 {
+        manager_client.add_manager(&Address::generate(&e), &0u32, &None);
+    }
+    assert_eq!(manager_client.managers().len(), 10);
+    let result = manager_client.try_add_manager(&Address::generate(&e), &0u32, &None);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(100))));
+
     // update bootstrapper
     let new_bootstrapper = Address::generate(&e);
     manager_client.set_backstop_bootstrapper(&new_bootstrapper);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "set_backstop_bootstrapper",
+        &frodo,
+        new_bootstrapper.clone().into_val(&e),
+    );
     assert_eq!(
         e.auths()[0],
         (
@@ -110,13 +229,16 @@ fn test_execute_comet_functions() {
     );
     assert_eq!(manager_client.backstop_bootstrapper(), new_bootstrapper);
 
-    // update backstops
-    let new_backstops = vec![
+    // add backstop
+    let new_backstop = Address::generate(&e);
+    manager_client.add_backstop(&new_backstop);
+    assert_last_event(
         &e,
-        Address::generate(&e),
-        contracts.backstop.address.clone(),
-    ];
-    manager_client.set_backstops(&new_backstops);
+        &manager_client.address,
+        "add_backstop",
+        &frodo,
+        new_backstop.clone().into_val(&e),
+    );
     assert_eq!(
         e.auths()[0],
         (
@@ -124,36 +246,91 @@ fn test_execute_comet_functions() {
             AuthorizedInvocation {
                 function: AuthorizedFunction::Contract((
                     manager_client.address.clone(),
-                    Symbol::new(&e, "set_backstops"),
-                    vec![&e, new_backstops.to_val(),]
+                    Symbol::new(&e, "add_backstop"),
+                    vec![&e, new_backstop.to_val(),]
                 )),
                 sub_invocations: std::vec![]
             }
         )
     );
-    assert_eq!(manager_client.backstops(), new_backstops);
+    assert_eq!(
+        manager_client.backstops(&0, &10),
+        vec![&e, contracts.backstop.address.clone(), new_backstop.clone()]
+    );
 
-    // update backstops - validate max contract list error
-    let five_contracts = vec![
+    // add backstop - validate already listed error
+    let result = manager_client.try_add_backstop(&new_backstop);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(122))));
+
+    // remove backstop
+    manager_client.remove_backstop(&contracts.backstop.address);
+    assert_last_event(
         &e,
-        Address::generate(&e),
-        Address::generate(&e),
-        Address::generate(&e),
-        Address::generate(&e),
-        Address::generate(&e),
-    ];
-    let result = manager_client.try_set_backstops(&five_contracts);
-    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(100))));
+        &manager_client.address,
+        "remove_backstop",
+        &frodo,
+        contracts.backstop.address.clone().into_val(&e),
+    );
+    assert_eq!(
+        e.auths()[0],
+        (
+            frodo.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "remove_backstop"),
+                    vec![&e, contracts.backstop.address.to_val(),]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    assert_eq!(
+        manager_client.backstops(&0, &10),
+        vec![&e, new_backstop.clone()]
+    );
+
+    // remove backstop - validate not listed error
+    let result = manager_client.try_remove_backstop(&contracts.backstop.address);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(123))));
+    manager_client.add_backstop(&contracts.backstop.address);
+
+    // add pools - validate the lists are not bounded
+    let mut new_pools = vec![&e, pool.clone()];
+    for _ in 0..20 {
+        let new_pool = Address::generate(&e);
+        manager_client.add_pool(&new_pool);
+        new_pools.push_back(new_pool);
+    }
+    assert_eq!(
+        e.auths()[0],
+        (
+            frodo.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "add_pool"),
+                    vec![&e, new_pools.last().unwrap().to_val(),]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    assert_eq!(manager_client.pools(&0, &100), new_pools);
+    assert_eq!(manager_client.pools(&5, &3), new_pools.slice(5..8));
+    assert_eq!(manager_client.pools(&20, &3), new_pools.slice(20..21));
+    assert_eq!(manager_client.pools(&21, &3).len(), 0);
 
-    // update pools
-    let new_pools = vec![
+    // remove pool - validate the last pool is moved into the removed position
+    let removed_pool = new_pools.get(3).unwrap();
+    manager_client.remove_pool(&removed_pool);
+    assert_last_event(
         &e,
-        Address::generate(&e),
-        Address::generate(&e),
-        pool.clone(),
-        Address::generate(&e),
-    ];
-    manager_client.set_pools(&new_pools);
+        &manager_client.address,
+        "remove_pool",
+        &frodo,
+        removed_pool.clone().into_val(&e),
+    );
     assert_eq!(
         e.auths()[0],
         (
@@ -161,18 +338,21 @@ fn test_execute_comet_functions() {
             AuthorizedInvocation {
                 function: AuthorizedFunction::Contract((
                     manager_client.address.clone(),
-                    Symbol::new(&e, "set_pools"),
-                    vec![&e, new_pools.to_val(),]
+                    Symbol::new(&e, "remove_pool"),
+                    vec![&e, removed_pool.to_val(),]
                 )),
                 sub_invocations: std::vec![]
             }
         )
     );
-    assert_eq!(manager_client.pools(), new_pools);
+    let pools = manager_client.pools(&0, &100);
+    assert_eq!(pools.len(), 20);
+    assert_eq!(pools.get(3).unwrap(), new_pools.last().unwrap());
+    assert!(!pools.contains(&removed_pool));
 
-    // update pools - validate max contract list error
-    let result = manager_client.try_set_pools(&five_contracts);
-    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(100))));
+    // remove pool - validate not listed error
+    let result = manager_client.try_remove_pool(&removed_pool);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(123))));
 
     /***** TOKEN TRANSFERS ******/
 
@@ -197,6 +377,13 @@ fn test_execute_comet_functions() {
         }])
         .transfer_token(&blnd.address(), &samwise, &transfer_amount);
     assert_eq!(e.auths()[0].0, frodo); // assert require_auth exists
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "transfer_token",
+        &frodo,
+        (blnd.address(), samwise.clone(), transfer_amount).into_val(&e),
+    );
     let samwise_balance_blnd_1 = blnd_token.balance(&samwise);
     let balance_blnd_1 = blnd_token.balance(&manager_client.address);
     assert_eq!(
@@ -226,8 +413,1763 @@ fn test_execute_comet_functions() {
         }])
         .refund_token(&merry, &contracts.backstop_token.address, &refund_amount);
     assert_eq!(e.auths()[0].0, merry); // assert require_auth exists
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "refund_token",
+        &merry,
+        (contracts.backstop_token.address.clone(), refund_amount).into_val(&e),
+    );
     let frodo_balance_lp_1 = contracts.backstop_token.balance(&frodo);
     let balance_lp_1 = contracts.backstop_token.balance(&manager_client.address);
     assert_eq!(frodo_balance_lp_1, frodo_balance_lp_0 + refund_amount);
     assert_eq!(balance_lp_1, lp_balance_0 - refund_amount);
 }
+
+#[test]
+fn test_ownership_transfer() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let merry = Address::generate(&e);
+
+    // bootstrapper and backstop token are never invoked during ownership management
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &merry,
+        &0,
+        &Address::generate(&e),
+        &Address::generate(&e),
+        &vec![&e, Address::generate(&e)],
+        &vec![&e, Address::generate(&e)],
+        &0,
+    );
+    assert_eq!(manager_client.owner(), frodo);
+    assert!(manager_client.pending_owner().is_none());
+
+    // accept - validate a proposal must exist
+    let result = manager_client.try_accept_owner();
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(105))));
+
+    // cancel - validate a proposal must exist
+    let result = manager_client.try_cancel_owner_transfer();
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(105))));
+
+    // propose - validate expiration must be in the future
+    let result = manager_client.try_propose_owner(&samwise, &e.ledger().sequence());
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(104))));
+
+    // propose
+    let expiration_ledger = e.ledger().sequence() + ONE_DAY_LEDGERS;
+    manager_client.propose_owner(&samwise, &expiration_ledger);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "propose_owner",
+        &frodo,
+        (samwise.clone(), expiration_ledger).into_val(&e),
+    );
+    assert_eq!(
+        e.auths()[0],
+        (
+            frodo.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "propose_owner"),
+                    vec![&e, samwise.to_val(), expiration_ledger.into_val(&e)]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    let pending_owner = manager_client.pending_owner().unwrap();
+    assert_eq!(pending_owner.id, samwise);
+    assert_eq!(pending_owner.expiration_ledger, expiration_ledger);
+    assert_eq!(manager_client.owner(), frodo);
+
+    // cancel
+    manager_client.cancel_owner_transfer();
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "cancel_owner_transfer",
+        &frodo,
+        samwise.clone().into_val(&e),
+    );
+    assert_eq!(
+        e.auths()[0],
+        (
+            frodo.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "cancel_owner_transfer"),
+                    vec![&e]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    assert!(manager_client.pending_owner().is_none());
+
+    // accept - validate an expired proposal cannot be accepted
+    let expiration_ledger = e.ledger().sequence() + ONE_DAY_LEDGERS;
+    manager_client.propose_owner(&samwise, &expiration_ledger);
+    e.jump(ONE_DAY_LEDGERS + 1);
+    let result = manager_client.try_accept_owner();
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(106))));
+    assert_eq!(manager_client.owner(), frodo);
+
+    // accept - validate the current owner cannot accept on behalf of the pending owner
+    let expiration_ledger = e.ledger().sequence() + ONE_DAY_LEDGERS;
+    manager_client.propose_owner(&samwise, &expiration_ledger);
+    e.set_auths(&[]);
+    let result = manager_client
+        .mock_auths(&[MockAuth {
+            address: &frodo,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: &"accept_owner",
+                args: vec![&e],
+                sub_invokes: &[],
+            },
+        }])
+        .try_accept_owner();
+    assert!(result.is_err());
+
+    // accept
+    e.set_auths(&[]);
+    manager_client
+        .mock_auths(&[MockAuth {
+            address: &samwise,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: &"accept_owner",
+                args: vec![&e],
+                sub_invokes: &[],
+            },
+        }])
+        .accept_owner();
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "accept_owner",
+        &samwise,
+        frodo.clone().into_val(&e),
+    );
+    assert_eq!(
+        e.auths()[0],
+        (
+            samwise.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "accept_owner"),
+                    vec![&e]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    assert_eq!(manager_client.owner(), samwise);
+    assert!(manager_client.pending_owner().is_none());
+
+    // validate the previous owner can no longer act as owner
+    e.set_auths(&[]);
+    let result = manager_client
+        .mock_auths(&[MockAuth {
+            address: &frodo,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: &"update_manager",
+                args: vec![&e, merry.into_val(&e), 2u32.into_val(&e)],
+                sub_invokes: &[],
+            },
+        }])
+        .try_update_manager(&merry, &2u32);
+    assert!(result.is_err());
+    assert_eq!(
+        manager_client.managers().get(merry).unwrap().permissions,
+        SCOPE_LOW
+    );
+}
+
+#[test]
+fn test_legacy_manager_migration() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let merry = Address::generate(&e);
+
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &merry,
+        &0,
+        &Address::generate(&e),
+        &Address::generate(&e),
+        &vec![&e, Address::generate(&e)],
+        &vec![&e, Address::generate(&e)],
+        &0,
+    );
+
+    // replace the managers map with a legacy single manager record
+    e.as_contract(&manager_client.address, || {
+        let storage = e.storage().instance();
+        storage.remove(&Symbol::new(&e, "Managers"));
+        storage.set(
+            &Symbol::new(&e, "Manager"),
+            &Manager {
+                id: samwise.clone(),
+                scope: 1,
+            },
+        );
+    });
+
+    // the legacy manager is read as the only manager
+    let managers = manager_client.managers();
+    assert_eq!(managers.len(), 1);
+    assert_eq!(
+        managers.get(samwise.clone()).unwrap().permissions,
+        SCOPE_MEDIUM
+    );
+    assert_eq!(
+        managers.get(samwise.clone()).unwrap().expires_at_ledger,
+        None
+    );
+
+    // the legacy manager can still act at their scope
+    let token = e.register_stellar_asset_contract_v2(frodo.clone());
+    manager_client.refund_token(&samwise, &token.address(), &0);
+    assert_eq!(e.auths()[0].0, samwise);
+
+    // writing the managers map migrates the legacy manager
+    manager_client.add_manager(&merry, &2u32, &None);
+    let managers = manager_client.managers();
+    assert_eq!(managers.len(), 2);
+    assert_eq!(
+        managers.get(samwise.clone()).unwrap().permissions,
+        SCOPE_MEDIUM
+    );
+    assert_eq!(managers.get(merry.clone()).unwrap().permissions, SCOPE_HIGH);
+    e.as_contract(&manager_client.address, || {
+        assert!(!e.storage().instance().has(&Symbol::new(&e, "Manager")));
+    });
+}
+
+#[test]
+fn test_manager_permissions() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let token = e.register_stellar_asset_contract_v2(frodo.clone());
+
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &samwise,
+        &2,
+        &Address::generate(&e),
+        &Address::generate(&e),
+        &vec![&e, Address::generate(&e)],
+        &vec![&e, Address::generate(&e)],
+        &0,
+    );
+    assert_eq!(
+        manager_client
+            .managers()
+            .get(samwise.clone())
+            .unwrap()
+            .permissions,
+        ALL
+    );
+
+    // set permissions - grant only withdrawals
+    manager_client.set_manager_permissions(&samwise, &B_WITHDRAW);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "update_manager",
+        &frodo,
+        (samwise.clone(), ALL, B_WITHDRAW).into_val(&e),
+    );
+    assert_eq!(
+        e.auths()[0],
+        (
+            frodo.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "set_manager_permissions"),
+                    vec![&e, samwise.to_val(), B_WITHDRAW.into_val(&e)]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    assert_eq!(
+        manager_client
+            .managers()
+            .get(samwise.clone())
+            .unwrap()
+            .permissions,
+        B_WITHDRAW
+    );
+
+    // - validate the granted permission passes authorization (and fails on contract validation)
+    let random_address = Address::generate(&e);
+    let result =
+        manager_client.try_b_withdraw(&samwise, &random_address, &random_address, &1_0000000);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(101))));
+
+    // - validate permissions of lower scope presets are not implied
+    let result = manager_client.try_refund_token(&samwise, &token.address(), &0);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(4))));
+    let result = manager_client.try_b_claim(&samwise, &random_address, &random_address, &1_0000000);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(4))));
+    let result =
+        manager_client.try_b_deposit(&samwise, &random_address, &random_address, &1_0000000);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(4))));
+
+    // set permissions - grant refunds and withdrawals
+    manager_client.set_manager_permissions(&samwise, &(B_WITHDRAW | REFUND_TOKEN));
+    manager_client.refund_token(&samwise, &token.address(), &0);
+    assert_eq!(e.auths()[0].0, samwise);
+
+    // set permissions - validate unknown permission bits error
+    let result = manager_client.try_set_manager_permissions(&samwise, &(ALL + 1));
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(109))));
+
+    // set permissions - validate manager must exist
+    let result = manager_client.try_set_manager_permissions(&frodo, &B_CLAIM);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(108))));
+
+    // set permissions - no permissions leaves the manager unable to act
+    manager_client.set_manager_permissions(&samwise, &0);
+    let result = manager_client.try_refund_token(&samwise, &token.address(), &0);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(4))));
+}
+
+#[test]
+fn test_manager_expiration() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let merry = Address::generate(&e);
+    let token = e.register_stellar_asset_contract_v2(frodo.clone());
+
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &samwise,
+        &0,
+        &Address::generate(&e),
+        &Address::generate(&e),
+        &vec![&e, Address::generate(&e)],
+        &vec![&e, Address::generate(&e)],
+        &0,
+    );
+
+    // add manager - validate expiration must be in the future
+    let result = manager_client.try_add_manager(&merry, &0u32, &Some(e.ledger().sequence()));
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(104))));
+
+    // add manager - with expiration
+    let expires_at_ledger = e.ledger().sequence() + 7 * ONE_DAY_LEDGERS;
+    manager_client.add_manager(&merry, &0u32, &Some(expires_at_ledger));
+    assert_eq!(
+        manager_client
+            .managers()
+            .get(merry.clone())
+            .unwrap()
+            .expires_at_ledger,
+        Some(expires_at_ledger)
+    );
+
+    // - manager can act up to and including the expiration ledger
+    e.jump(7 * ONE_DAY_LEDGERS);
+    manager_client.refund_token(&merry, &token.address(), &0);
+    assert_eq!(e.auths()[0].0, merry);
+
+    // - manager is rejected after the expiration ledger
+    e.jump(1);
+    let result = manager_client.try_refund_token(&merry, &token.address(), &0);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(110))));
+
+    // - updating permissions does not renew the manager
+    manager_client.update_manager(&merry, &2u32);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "update_manager",
+        &frodo,
+        (merry.clone(), SCOPE_LOW, SCOPE_HIGH).into_val(&e),
+    );
+    let result = manager_client.try_refund_token(&merry, &token.address(), &0);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(110))));
+
+    // - managers without an expiration are unaffected
+    manager_client.refund_token(&samwise, &token.address(), &0);
+    assert_eq!(e.auths()[0].0, samwise);
+
+    // renew manager - validate expiration must be in the future
+    let result = manager_client.try_renew_manager(&merry, &Some(e.ledger().sequence()));
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(104))));
+
+    // renew manager - validate manager must exist
+    let result = manager_client.try_renew_manager(&frodo, &None);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(108))));
+
+    // renew manager
+    let expires_at_ledger = e.ledger().sequence() + ONE_DAY_LEDGERS;
+    manager_client.renew_manager(&merry, &Some(expires_at_ledger));
+    assert_eq!(
+        e.auths()[0],
+        (
+            frodo.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "renew_manager"),
+                    vec![&e, merry.to_val(), Some(expires_at_ledger).into_val(&e)]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    let manager_config = manager_client.managers().get(merry.clone()).unwrap();
+    assert_eq!(manager_config.expires_at_ledger, Some(expires_at_ledger));
+    assert_eq!(manager_config.permissions, SCOPE_HIGH);
+    manager_client.refund_token(&merry, &token.address(), &0);
+    assert_eq!(e.auths()[0].0, merry);
+
+    // renew manager - remove the expiration
+    e.jump(ONE_DAY_LEDGERS + 1);
+    let result = manager_client.try_refund_token(&merry, &token.address(), &0);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(110))));
+    manager_client.renew_manager(&merry, &None);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "renew_manager",
+        &frodo,
+        (merry.clone(), Some(expires_at_ledger), None::<u32>).into_val(&e),
+    );
+    assert_eq!(
+        manager_client
+            .managers()
+            .get(merry.clone())
+            .unwrap()
+            .expires_at_ledger,
+        None
+    );
+    manager_client.refund_token(&merry, &token.address(), &0);
+    assert_eq!(e.auths()[0].0, merry);
+}
+
+#[test]
+fn test_manager_limits() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let token = e.register_stellar_asset_contract_v2(frodo.clone());
+    let token_client = TokenClient::new(&e, &token.address());
+
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &samwise,
+        &0,
+        &Address::generate(&e),
+        &Address::generate(&e),
+        &vec![&e, Address::generate(&e)],
+        &vec![&e, Address::generate(&e)],
+        &0,
+    );
+    StellarAssetClient::new(&e, &token.address()).mint(&manager_client.address, &1000_0000000);
+
+    let refund_key = LimitKey::Refund(token.address());
+    assert_eq!(manager_client.limits().len(), 0);
+    assert_eq!(manager_client.remaining_limit(&refund_key), None);
+
+    // set limit - validate limit is valid
+    let result = manager_client.try_set_limit(&refund_key, &-1, &ONE_DAY_LEDGERS);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(111))));
+    let result = manager_client.try_set_limit(&refund_key, &100_0000000, &0);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(111))));
+
+    // set limit
+    let max_amount: i128 = 100_0000000;
+    manager_client.set_limit(&refund_key, &max_amount, &ONE_DAY_LEDGERS);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "set_limit",
+        &frodo,
+        (refund_key.clone(), max_amount, ONE_DAY_LEDGERS).into_val(&e),
+    );
+    assert_eq!(
+        e.auths()[0],
+        (
+            frodo.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "set_limit"),
+                    vec![
+                        &e,
+                        refund_key.into_val(&e),
+                        max_amount.into_val(&e),
+                        ONE_DAY_LEDGERS.into_val(&e)
+                    ]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    let limit = manager_client.limits().get(refund_key.clone()).unwrap();
+    assert_eq!(limit.max_amount, max_amount);
+    assert_eq!(limit.period, ONE_DAY_LEDGERS);
+    assert_eq!(
+        manager_client.remaining_limit(&refund_key),
+        Some(max_amount)
+    );
+
+    // refund - manager uses part of the limit
+    manager_client.refund_token(&samwise, &token.address(), &60_0000000);
+    assert_eq!(token_client.balance(&frodo), 60_0000000);
+    assert_eq!(
+        manager_client.remaining_limit(&refund_key),
+        Some(40_0000000)
+    );
+
+    // refund - validate manager cannot exceed the limit
+    let result = manager_client.try_refund_token(&samwise, &token.address(), &40_0000001);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(114))));
+
+    // refund - validate the owner is not limited and does not use the limit
+    manager_client.refund_token(&frodo, &token.address(), &500_0000000);
+    assert_eq!(token_client.balance(&frodo), 560_0000000);
+    assert_eq!(
+        manager_client.remaining_limit(&refund_key),
+        Some(40_0000000)
+    );
+
+    // refund - validate limits are per token
+    let other_token = e.register_stellar_asset_contract_v2(frodo.clone());
+    manager_client.refund_token(&samwise, &other_token.address(), &0);
+
+    // refund - limit resets after the period
+    e.jump(ONE_DAY_LEDGERS);
+    assert_eq!(
+        manager_client.remaining_limit(&refund_key),
+        Some(max_amount)
+    );
+    manager_client.refund_token(&samwise, &token.address(), &max_amount);
+    assert_eq!(manager_client.remaining_limit(&refund_key), Some(0));
+    let result = manager_client.try_refund_token(&samwise, &token.address(), &1);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(114))));
+
+    // remove limit
+    manager_client.remove_limit(&refund_key);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "remove_limit",
+        &frodo,
+        refund_key.clone().into_val(&e),
+    );
+    assert_eq!(
+        e.auths()[0],
+        (
+            frodo.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "remove_limit"),
+                    vec![&e, refund_key.into_val(&e)]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    assert_eq!(manager_client.limits().len(), 0);
+    assert_eq!(manager_client.remaining_limit(&refund_key), None);
+    manager_client.refund_token(&samwise, &token.address(), &1);
+
+    // remove limit - validate limit must exist
+    let result = manager_client.try_remove_limit(&refund_key);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(111))));
+}
+
+#[test]
+fn test_guardian_pause() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let gandalf = Address::generate(&e);
+    let token = e.register_stellar_asset_contract_v2(frodo.clone());
+
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &samwise,
+        &2,
+        &Address::generate(&e),
+        &Address::generate(&e),
+        &vec![&e, Address::generate(&e)],
+        &vec![&e, Address::generate(&e)],
+        &0,
+    );
+    assert_eq!(manager_client.guardian(), None);
+    assert!(!manager_client.paused());
+
+    // pause - validate a guardian must be set
+    let result = manager_client.try_pause();
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(4))));
+
+    // set guardian
+    manager_client.set_guardian(&Some(gandalf.clone()));
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "set_guardian",
+        &frodo,
+        Some(gandalf.clone()).into_val(&e),
+    );
+    assert_eq!(
+        e.auths()[0],
+        (
+            frodo.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "set_guardian"),
+                    vec![&e, Some(gandalf.clone()).into_val(&e)]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    assert_eq!(manager_client.guardian(), Some(gandalf.clone()));
+
+    // pause - validate only the guardian can pause
+    e.set_auths(&[]);
+    let result = manager_client
+        .mock_auths(&[MockAuth {
+            address: &samwise,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: &"pause",
+                args: vec![&e],
+                sub_invokes: &[],
+            },
+        }])
+        .try_pause();
+    assert!(result.is_err());
+    assert!(!manager_client.paused());
+
+    // pause
+    e.set_auths(&[]);
+    manager_client
+        .mock_auths(&[MockAuth {
+            address: &gandalf,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: &"pause",
+                args: vec![&e],
+                sub_invokes: &[],
+            },
+        }])
+        .pause();
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "pause",
+        &gandalf,
+        ().into_val(&e),
+    );
+    assert_eq!(
+        e.auths()[0],
+        (
+            gandalf.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "pause"),
+                    vec![&e]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    assert!(manager_client.paused());
+
+    // validate every manager action reverts while paused
+    e.mock_all_auths();
+    let backstop = Address::generate(&e);
+    let pool = Address::generate(&e);
+    let paused_error = Some(Ok(Error::from_contract_error(115)));
+    assert_eq!(
+        manager_client
+            .try_refund_token(&samwise, &token.address(), &0)
+            .err(),
+        paused_error
+    );
+    assert_eq!(
+        manager_client
+            .try_b_claim(&samwise, &backstop, &pool, &0)
+            .err(),
+        paused_error
+    );
+    assert_eq!(
+        manager_client
+            .try_b_deposit(&samwise, &backstop, &pool, &1)
+            .err(),
+        paused_error
+    );
+    assert_eq!(
+        manager_client
+            .try_b_queue_withdrawal(&samwise, &backstop, &pool, &1)
+            .err(),
+        paused_error
+    );
+    assert_eq!(
+        manager_client
+            .try_b_dequeue_withdrawal(&samwise, &backstop, &pool, &1)
+            .err(),
+        paused_error
+    );
+    assert_eq!(
+        manager_client
+            .try_b_withdraw(&samwise, &backstop, &pool, &1)
+            .err(),
+        paused_error
+    );
+    assert_eq!(
+        manager_client
+            .try_c_join_pool(&samwise, &1, &vec![&e, 1, 1])
+            .err(),
+        paused_error
+    );
+    assert_eq!(
+        manager_client
+            .try_c_exit_pool(&samwise, &1, &vec![&e, 0, 0])
+            .err(),
+        paused_error
+    );
+    assert_eq!(
+        manager_client
+            .try_bb_start_bootstrap(&samwise, &0, &1, &1, &ONE_DAY_LEDGERS, &pool)
+            .err(),
+        paused_error
+    );
+    assert_eq!(
+        manager_client
+            .try_bb_claim_bootstrap(&samwise, &0, &backstop)
+            .err(),
+        paused_error
+    );
+    assert_eq!(
+        manager_client.try_bb_refund_bootstrap(&samwise, &0).err(),
+        paused_error
+    );
+    assert_eq!(
+        manager_client
+            .try_b_withdraw_unlocked(&samwise, &backstop, &pool)
+            .err(),
+        paused_error
+    );
+    assert_eq!(
+        manager_client
+            .try_b_compound(&samwise, &backstop, &vec![&e, pool.clone()], &0)
+            .err(),
+        paused_error
+    );
+    assert_eq!(
+        manager_client
+            .try_b_migrate(&samwise, &backstop, &Address::generate(&e), &pool, &1)
+            .err(),
+        paused_error
+    );
+    assert_eq!(
+        manager_client
+            .try_b_rebalance(&samwise, &backstop, &pool, &Address::generate(&e), &1)
+            .err(),
+        paused_error
+    );
+    assert_eq!(
+        manager_client.try_cancel_rebalance(&samwise, &0).err(),
+        paused_error
+    );
+    assert_eq!(
+        manager_client.try_execute_rebalance(&samwise, &0).err(),
+        paused_error
+    );
+    assert_eq!(
+        manager_client.try_c_join_single(&samwise, &0, &1, &1).err(),
+        paused_error
+    );
+    assert_eq!(
+        manager_client.try_c_exit_single(&samwise, &0, &1, &0).err(),
+        paused_error
+    );
+
+    // validate the owner can still act while paused
+    manager_client.refund_token(&frodo, &token.address(), &0);
+    assert_eq!(e.auths()[0].0, frodo);
+
+    // unpause - validate only the owner can unpause
+    e.set_auths(&[]);
+    let result = manager_client
+        .mock_auths(&[MockAuth {
+            address: &gandalf,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: &"unpause",
+                args: vec![&e],
+                sub_invokes: &[],
+            },
+        }])
+        .try_unpause();
+    assert!(result.is_err());
+    assert!(manager_client.paused());
+
+    // unpause
+    e.set_auths(&[]);
+    manager_client
+        .mock_auths(&[MockAuth {
+            address: &frodo,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: &"unpause",
+                args: vec![&e],
+                sub_invokes: &[],
+            },
+        }])
+        .unpause();
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "unpause",
+        &frodo,
+        ().into_val(&e),
+    );
+    assert_eq!(
+        e.auths()[0],
+        (
+            frodo.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "unpause"),
+                    vec![&e]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    assert!(!manager_client.paused());
+    e.mock_all_auths();
+    manager_client.refund_token(&samwise, &token.address(), &0);
+    assert_eq!(e.auths()[0].0, samwise);
+
+    // set guardian - remove the guardian
+    manager_client.set_guardian(&None);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "set_guardian",
+        &frodo,
+        None::<Address>.into_val(&e),
+    );
+    assert_eq!(manager_client.guardian(), None);
+    let result = manager_client.try_pause();
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(4))));
+}
+
+#[test]
+fn test_timelock() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let merry = Address::generate(&e);
+    let backstop = Address::generate(&e);
+    let pool = Address::generate(&e);
+    let new_pool = Address::generate(&e);
+    let timelock = ONE_DAY_LEDGERS;
+
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &samwise,
+        &0,
+        &Address::generate(&e),
+        &Address::generate(&e),
+        &vec![&e, backstop.clone()],
+        &vec![&e, pool.clone()],
+        &timelock,
+    );
+    assert_eq!(manager_client.timelock(), timelock);
+    assert_eq!(manager_client.pending_changes().len(), 0);
+
+    // add pool - validate the change is queued
+    manager_client.add_pool(&new_pool);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "queue_change",
+        &frodo,
+        (
+            0u32,
+            PendingChange {
+                change: ConfigChange::AddPool(new_pool.clone()),
+                execute_after: 100 + timelock,
+            },
+        )
+            .into_val(&e),
+    );
+    assert_eq!(
+        e.auths()[0],
+        (
+            frodo.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "add_pool"),
+                    vec![&e, new_pool.into_val(&e)]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    assert_eq!(manager_client.pools(&0, &10), vec![&e, pool.clone()]);
+    let pending_changes = manager_client.pending_changes();
+    assert_eq!(pending_changes.len(), 1);
+    let pending_change = pending_changes.get(0).unwrap();
+    assert_eq!(pending_change.execute_after, 100 + timelock);
+
+    // add manager - validate the change is queued
+    manager_client.add_manager(&merry, &1u32, &None);
+    assert!(manager_client.managers().get(merry.clone()).is_none());
+    assert_eq!(manager_client.pending_changes().len(), 2);
+
+    // add manager - validate stateless checks happen when queued
+    let result = manager_client.try_add_manager(&merry, &3u32, &None);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(102))));
+    let result = manager_client.try_set_timelock(&(ONE_DAY_LEDGERS * 31));
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(118))));
+
+    // remove manager - validate it is not timelocked
+    manager_client.remove_manager(&samwise);
+    assert_eq!(manager_client.managers().len(), 0);
+
+    // execute pending - validate timelock must pass
+    e.jump(timelock - 1);
+    let result = manager_client.try_execute_pending(&0);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(117))));
+
+    // execute pending - validate anyone can execute
+    e.jump(1);
+    e.set_auths(&[]);
+    manager_client.execute_pending(&0);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "add_pool",
+        &frodo,
+        new_pool.clone().into_val(&e),
+    );
+    assert_eq!(e.auths().len(), 0);
+    assert_eq!(
+        manager_client.pools(&0, &10),
+        vec![&e, pool.clone(), new_pool.clone()]
+    );
+    assert_eq!(manager_client.pending_changes().len(), 1);
+
+    // execute pending - validate changes can only be executed once
+    let result = manager_client.try_execute_pending(&0);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(116))));
+
+    // cancel pending - validate only the owner can cancel
+    let result = manager_client
+        .mock_auths(&[MockAuth {
+            address: &samwise,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: &"cancel_pending",
+                args: vec![&e, 1u32.into_val(&e)],
+                sub_invokes: &[],
+            },
+        }])
+        .try_cancel_pending(&1);
+    assert!(result.is_err());
+
+    // cancel pending
+    e.set_auths(&[]);
+    manager_client
+        .mock_auths(&[MockAuth {
+            address: &frodo,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: &"cancel_pending",
+                args: vec![&e, 1u32.into_val(&e)],
+                sub_invokes: &[],
+            },
+        }])
+        .cancel_pending(&1);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "cancel_pending",
+        &frodo,
+        1u32.into_val(&e),
+    );
+    assert_eq!(
+        e.auths()[0],
+        (
+            frodo.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "cancel_pending"),
+                    vec![&e, 1u32.into_val(&e)]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    assert_eq!(manager_client.pending_changes().len(), 0);
+    let result = manager_client.try_execute_pending(&1);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(116))));
+    assert!(manager_client.managers().get(merry.clone()).is_none());
+
+    // set timelock - validate disabling the timelock is also delayed
+    e.mock_all_auths();
+    manager_client.set_timelock(&0);
+    assert_eq!(manager_client.timelock(), timelock);
+
+    // upgrade - validate the upgrade is queued
+    let wasm_hash = e.deployer().upload_contract_wasm(contract::WASM);
+    manager_client.upgrade(&wasm_hash);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "queue_change",
+        &frodo,
+        (
+            3u32,
+            PendingChange {
+                change: ConfigChange::Upgrade(wasm_hash.clone()),
+                execute_after: e.ledger().sequence() + timelock,
+            },
+        )
+            .into_val(&e),
+    );
+    assert_eq!(manager_client.pending_changes().len(), 2);
+
+    e.jump(timelock);
+    manager_client.execute_pending(&2);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "set_timelock",
+        &frodo,
+        (timelock, 0u32).into_val(&e),
+    );
+    assert_eq!(manager_client.timelock(), 0);
+    manager_client.execute_pending(&3);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "upgrade",
+        &frodo,
+        wasm_hash.into_val(&e),
+    );
+
+    // add manager - validate changes apply immediately without a timelock
+    manager_client.add_manager(&merry, &1u32, &None);
+    assert_eq!(
+        manager_client
+            .managers()
+            .get(merry.clone())
+            .unwrap()
+            .permissions,
+        SCOPE_MEDIUM
+    );
+    assert_eq!(manager_client.pending_changes().len(), 0);
+}
+
+#[test]
+fn test_upgrade_and_migrate() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let usdc = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let blnd = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let (contracts, pool) = create_blend_contracts(&e, &bombadil, &blnd.address(), &usdc.address());
+    let backstop = contracts.backstop.address.clone();
+
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &samwise,
+        &0,
+        &Address::generate(&e),
+        &Address::generate(&e),
+        &vec![&e],
+        &vec![&e],
+        &0,
+    );
+    assert_eq!(manager_client.schema_version(), 3);
+    let wasm_hash = e.deployer().upload_contract_wasm(contract::WASM);
+
+    // upgrade - validate only the owner can upgrade
+    let result = manager_client
+        .mock_auths(&[MockAuth {
+            address: &samwise,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: &"upgrade",
+                args: vec![&e, wasm_hash.into_val(&e)],
+                sub_invokes: &[],
+            },
+        }])
+        .try_upgrade(&wasm_hash);
+    assert!(result.is_err());
+
+    // upgrade
+    e.set_auths(&[]);
+    manager_client
+        .mock_auths(&[MockAuth {
+            address: &frodo,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: &"upgrade",
+                args: vec![&e, wasm_hash.into_val(&e)],
+                sub_invokes: &[],
+            },
+        }])
+        .upgrade(&wasm_hash);
+    assert_eq!(
+        e.auths()[0],
+        (
+            frodo.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "upgrade"),
+                    vec![&e, wasm_hash.into_val(&e)]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "upgrade",
+        &frodo,
+        wasm_hash.into_val(&e),
+    );
+    assert_eq!(manager_client.owner(), frodo);
+
+    // migrate - validate storage is already at the current version
+    e.mock_all_auths();
+    let result = manager_client.try_migrate();
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(119))));
+
+    // replace the storage with a version 0 layout
+    e.as_contract(&manager_client.address, || {
+        let storage = e.storage().instance();
+        storage.remove(&Symbol::new(&e, "Version"));
+        storage.remove(&Symbol::new(&e, "Managers"));
+        storage.set(&Symbol::new(&e, "Bstop"), &vec![&e, backstop.clone()]);
+        storage.set(&Symbol::new(&e, "Pools"), &vec![&e, pool.clone()]);
+        storage.set(
+            &Symbol::new(&e, "Manager"),
+            &Manager {
+                id: samwise.clone(),
+                scope: 2,
+            },
+        );
+    });
+    assert_eq!(manager_client.schema_version(), 0);
+
+    // validate the legacy allowlists are read until they are migrated
+    assert_eq!(
+        manager_client.backstops(&0, &10),
+        vec![&e, backstop.clone()]
+    );
+    assert_eq!(manager_client.pools(&0, &10), vec![&e, pool.clone()]);
+    let pairs = manager_client.pairs(&0, &10);
+    assert_eq!(pairs.len(), 1);
+    assert_eq!(pairs.get(0).unwrap().backstop, backstop);
+    assert_eq!(pairs.get(0).unwrap().pool, pool);
+    let tokens_out = manager_client.b_withdraw_unlocked(&samwise, &backstop, &pool);
+    assert_eq!(tokens_out, 0);
+    let result =
+        manager_client.try_b_withdraw_unlocked(&samwise, &backstop, &Address::generate(&e));
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(101))));
+
+    // migrate
+    e.set_auths(&[]);
+    manager_client
+        .mock_auths(&[MockAuth {
+            address: &frodo,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: &"migrate",
+                args: vec![&e],
+                sub_invokes: &[],
+            },
+        }])
+        .migrate();
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "migrate",
+        &frodo,
+        (0u32, 3u32).into_val(&e),
+    );
+    assert_eq!(e.auths()[0].0, frodo);
+    assert_eq!(manager_client.schema_version(), 3);
+    assert_eq!(
+        manager_client
+            .managers()
+            .get(samwise.clone())
+            .unwrap()
+            .permissions,
+        SCOPE_HIGH
+    );
+    assert_eq!(
+        manager_client.backstops(&0, &10),
+        vec![&e, backstop.clone()]
+    );
+    assert_eq!(manager_client.pools(&0, &10), vec![&e, pool.clone()]);
+    let pairs = manager_client.pairs(&0, &10);
+    assert_eq!(pairs.len(), 1);
+    assert_eq!(pairs.get(0).unwrap().backstop, backstop);
+    assert_eq!(pairs.get(0).unwrap().pool, pool);
+    e.as_contract(&manager_client.address, || {
+        let storage = e.storage().instance();
+        assert!(!storage.has(&Symbol::new(&e, "Manager")));
+        assert!(!storage.has(&Symbol::new(&e, "Bstop")));
+        assert!(!storage.has(&Symbol::new(&e, "Pools")));
+    });
+}
+
+#[test]
+fn test_pairs() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let backstop_v1 = Address::generate(&e);
+    let backstop_v2 = Address::generate(&e);
+    let pool_a = Address::generate(&e);
+    let pool_b = Address::generate(&e);
+
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &samwise,
+        &2,
+        &Address::generate(&e),
+        &Address::generate(&e),
+        &vec![&e, backstop_v1.clone(), backstop_v2.clone()],
+        &vec![&e, pool_a.clone(), pool_b.clone()],
+        &0,
+    );
+
+    // validate every initial backstop is paired with every initial pool
+    let pairs = manager_client.pairs(&0, &10);
+    assert_eq!(pairs.len(), 4);
+    for (i, (backstop, pool)) in [
+        (&backstop_v1, &pool_a),
+        (&backstop_v2, &pool_a),
+        (&backstop_v1, &pool_b),
+        (&backstop_v2, &pool_b),
+    ]
+    .iter()
+    .enumerate()
+    {
+        let pair = pairs.get(i as u32).unwrap();
+        assert_eq!(&&pair.backstop, backstop);
+        assert_eq!(&&pair.pool, pool);
+    }
+
+    // remove pair - validate only the owner can remove pairs
+    let result = manager_client
+        .mock_auths(&[MockAuth {
+            address: &samwise,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: &"remove_pair",
+                args: vec![&e, backstop_v1.into_val(&e), pool_b.into_val(&e)],
+                sub_invokes: &[],
+            },
+        }])
+        .try_remove_pair(&backstop_v1, &pool_b);
+    assert!(result.is_err());
+
+    // remove pair
+    e.set_auths(&[]);
+    manager_client
+        .mock_auths(&[MockAuth {
+            address: &frodo,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: &"remove_pair",
+                args: vec![&e, backstop_v1.into_val(&e), pool_b.into_val(&e)],
+                sub_invokes: &[],
+            },
+        }])
+        .remove_pair(&backstop_v1, &pool_b);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "remove_pair",
+        &frodo,
+        Pair {
+            backstop: backstop_v1.clone(),
+            pool: pool_b.clone(),
+        }
+        .into_val(&e),
+    );
+    assert_eq!(
+        e.auths()[0],
+        (
+            frodo.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "remove_pair"),
+                    vec![&e, backstop_v1.into_val(&e), pool_b.into_val(&e)]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    assert_eq!(manager_client.pairs(&0, &10).len(), 3);
+    e.mock_all_auths();
+    let result = manager_client.try_remove_pair(&backstop_v1, &pool_b);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(121))));
+
+    // validate manager actions require a valid pair
+    let result = manager_client.try_b_claim(&samwise, &backstop_v1, &pool_b, &0);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(101))));
+    let result = manager_client.try_b_deposit(&samwise, &backstop_v1, &pool_b, &1);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(101))));
+    let result = manager_client.try_b_queue_withdrawal(&samwise, &backstop_v1, &pool_b, &1);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(101))));
+    let result = manager_client.try_b_dequeue_withdrawal(&samwise, &backstop_v1, &pool_b, &1);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(101))));
+    let result = manager_client.try_b_withdraw(&samwise, &backstop_v1, &pool_b, &1);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(101))));
+    // the pair check passes, and the call fails at the (non-existent) backstop
+    let result = manager_client.try_b_claim(&samwise, &backstop_v2, &pool_b, &0);
+    assert_ne!(result.err(), Some(Ok(Error::from_contract_error(101))));
+
+    // add pair
+    e.set_auths(&[]);
+    manager_client
+        .mock_auths(&[MockAuth {
+            address: &frodo,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: &"add_pair",
+                args: vec![&e, backstop_v1.into_val(&e), pool_b.into_val(&e)],
+                sub_invokes: &[],
+            },
+        }])
+        .add_pair(&backstop_v1, &pool_b);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "add_pair",
+        &frodo,
+        Pair {
+            backstop: backstop_v1.clone(),
+            pool: pool_b.clone(),
+        }
+        .into_val(&e),
+    );
+    assert_eq!(
+        e.auths()[0],
+        (
+            frodo.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "add_pair"),
+                    vec![&e, backstop_v1.into_val(&e), pool_b.into_val(&e)]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    let pairs = manager_client.pairs(&0, &10);
+    assert_eq!(pairs.len(), 4);
+    assert_eq!(pairs.get(3).unwrap().backstop, backstop_v1);
+    assert_eq!(pairs.get(3).unwrap().pool, pool_b);
+
+    // add pair - validate duplicates and unlisted addresses
+    e.mock_all_auths();
+    let result = manager_client.try_add_pair(&backstop_v1, &pool_b);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(120))));
+    let result = manager_client.try_add_pair(&backstop_v1, &Address::generate(&e));
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(101))));
+    let result = manager_client.try_add_pair(&Address::generate(&e), &pool_a);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(101))));
+
+    // remove backstop - validate pairs with the backstop are removed
+    manager_client.remove_backstop(&backstop_v2);
+    let pairs = manager_client.pairs(&0, &10);
+    assert_eq!(pairs.len(), 2);
+    assert!(pairs.iter().all(|pair| pair.backstop == backstop_v1));
+    let result = manager_client.try_b_claim(&samwise, &backstop_v2, &pool_b, &0);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(101))));
+
+    // add backstop - validate removed pairs are not revived
+    manager_client.add_backstop(&backstop_v2);
+    assert_eq!(manager_client.pairs(&0, &10).len(), 2);
+    let result = manager_client.try_b_claim(&samwise, &backstop_v2, &pool_b, &0);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(101))));
+    manager_client.add_pair(&backstop_v2, &pool_b);
+
+    // remove pool - validate pairs with the pool are removed
+    manager_client.remove_pool(&pool_a);
+    let pairs = manager_client.pairs(&0, &10);
+    assert_eq!(pairs.len(), 2);
+    assert!(pairs.iter().all(|pair| pair.pool == pool_b));
+    let result = manager_client.try_b_claim(&samwise, &backstop_v1, &pool_a, &0);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(101))));
+    let result = manager_client.try_add_pair(&backstop_v1, &pool_a);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(101))));
+    manager_client.add_pool(&pool_a);
+    assert_eq!(manager_client.pairs(&0, &10).len(), 2);
+
+    // remove backstop - validate only the pairs with the backstop are removed
+    let backstop_v3 = Address::generate(&e);
+    manager_client.add_backstop(&backstop_v3);
+    manager_client.add_pair(&backstop_v3, &pool_a);
+    manager_client.add_pair(&backstop_v3, &pool_b);
+    assert_eq!(manager_client.pairs(&0, &10).len(), 4);
+    manager_client.remove_pool(&pool_a);
+    assert_eq!(manager_client.pairs(&0, &10).len(), 3);
+    manager_client.remove_backstop(&backstop_v3);
+    let pairs = manager_client.pairs(&0, &10);
+    assert_eq!(pairs.len(), 2);
+    assert!(pairs
+        .iter()
+        .all(|pair| pair.backstop != backstop_v3 && pair.pool == pool_b));
+    manager_client.add_pool(&pool_a);
+
+    // validate reading the lists keeps their entries alive
+    for _ in 0..3 {
+        e.jump(110 * 17280);
+        assert_eq!(manager_client.backstops(&0, &10).len(), 2);
+        assert_eq!(manager_client.pools(&0, &10).len(), 2);
+        assert_eq!(manager_client.pairs(&0, &10).len(), 2);
+    }
+    e.as_contract(&manager_client.address, || {
+        let storage = e.storage().persistent();
+        let pools_key = (Symbol::new(&e, "PairPools"), backstop_v1.clone());
+        let backstops_key = (Symbol::new(&e, "PairBstops"), pool_b.clone());
+        assert_eq!(
+            storage.get::<_, Vec<Address>>(&pools_key),
+            Some(vec![&e, pool_b.clone()])
+        );
+        assert_eq!(
+            storage.get::<_, Vec<Address>>(&backstops_key),
+            Some(vec![&e, backstop_v1.clone(), backstop_v2.clone()])
+        );
+    });
+}
+
+#[test]
+fn test_history() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let token = e.register_stellar_asset_contract_v2(frodo.clone());
+
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &samwise,
+        &0,
+        &Address::generate(&e),
+        &Address::generate(&e),
+        &vec![&e, Address::generate(&e)],
+        &vec![&e, Address::generate(&e)],
+        &0,
+    );
+    StellarAssetClient::new(&e, &token.address()).mint(&manager_client.address, &1000_0000000);
+
+    assert_eq!(manager_client.history_retention(), 50);
+    assert_eq!(manager_client.history(&0, &10).len(), 0);
+
+    // manager actions are recorded
+    let start_ledger = e.ledger().sequence();
+    manager_client.refund_token(&samwise, &token.address(), &1);
+    e.jump(1);
+    manager_client.refund_token(&samwise, &token.address(), &2);
+    e.jump(1);
+    manager_client.refund_token(&frodo, &token.address(), &3);
+
+    let history = manager_client.history(&0, &10);
+    assert_eq!(history.len(), 3);
+    let record = history.get(0).unwrap();
+    assert_eq!(record.action, Symbol::new(&e, "refund_token"));
+    assert_eq!(record.caller, frodo);
+    assert_eq!(record.ledger, start_ledger + 2);
+    assert_eq!(record.amounts, vec![&e, 3]);
+    let record = history.get(2).unwrap();
+    assert_eq!(record.caller, samwise);
+    assert_eq!(record.ledger, start_ledger);
+    assert_eq!(record.amounts, vec![&e, 1]);
+
+    // history is paginated from the most recent action
+    let page = manager_client.history(&1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().amounts, vec![&e, 2]);
+    assert_eq!(manager_client.history(&3, &10).len(), 0);
+
+    // set retention - validate retention is under the maximum
+    let result = manager_client.try_set_history_retention(&101);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(124))));
+
+    // set retention - shrinking the history removes the oldest actions
+    manager_client.set_history_retention(&2);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "set_history_retention",
+        &frodo,
+        (50u32, 2u32).into_val(&e),
+    );
+    assert_eq!(
+        e.auths()[0],
+        (
+            frodo.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    manager_client.address.clone(),
+                    Symbol::new(&e, "set_history_retention"),
+                    vec![&e, 2u32.into_val(&e)]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    assert_eq!(manager_client.history_retention(), 2);
+    let history = manager_client.history(&0, &10);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(0).unwrap().amounts, vec![&e, 3]);
+    assert_eq!(history.get(1).unwrap().amounts, vec![&e, 2]);
+
+    // a full history drops the oldest action
+    manager_client.refund_token(&samwise, &token.address(), &4);
+    let history = manager_client.history(&0, &10);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(0).unwrap().amounts, vec![&e, 4]);
+    assert_eq!(history.get(1).unwrap().amounts, vec![&e, 3]);
+
+    // set retention - growing the history keeps the recorded actions
+    manager_client.set_history_retention(&5);
+    manager_client.refund_token(&samwise, &token.address(), &5);
+    let history = manager_client.history(&0, &10);
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.get(0).unwrap().amounts, vec![&e, 5]);
+    assert_eq!(history.get(2).unwrap().amounts, vec![&e, 3]);
+
+    // set retention - zero disables the history
+    manager_client.set_history_retention(&0);
+    assert_eq!(manager_client.history(&0, &10).len(), 0);
+    manager_client.refund_token(&samwise, &token.address(), &6);
+    assert_eq!(manager_client.history(&0, &10).len(), 0);
+
+    // set retention - validate only the owner can set the retention
+    let result = manager_client
+        .mock_auths(&[MockAuth {
+            address: &samwise,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: &"set_history_retention",
+                args: vec![&e, 10u32.into_val(&e)],
+                sub_invokes: &[],
+            },
+        }])
+        .try_set_history_retention(&10);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_timelock_widening_changes() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let merry = Address::generate(&e);
+    let pippin = Address::generate(&e);
+    let timelock = ONE_DAY_LEDGERS;
+
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &samwise,
+        &0,
+        &Address::generate(&e),
+        &Address::generate(&e),
+        &vec![&e],
+        &vec![&e],
+        &timelock,
+    );
+
+    // set guardian - validate setting the first guardian applies immediately
+    manager_client.set_guardian(&Some(merry.clone()));
+    assert_eq!(manager_client.guardian(), Some(merry.clone()));
+    assert_eq!(manager_client.pending_changes().len(), 0);
+
+    // set guardian - validate replacing or removing the guardian is queued
+    manager_client.set_guardian(&Some(pippin.clone()));
+    manager_client.set_guardian(&None);
+    assert_eq!(manager_client.guardian(), Some(merry.clone()));
+    let pending_changes = manager_client.pending_changes();
+    assert_eq!(pending_changes.len(), 2);
+    assert_eq!(
+        pending_changes.get(0).unwrap().change,
+        ConfigChange::SetGuardian(Some(pippin.clone()))
+    );
+    assert_eq!(
+        pending_changes.get(1).unwrap().change,
+        ConfigChange::SetGuardian(None)
+    );
+
+    // set limit - validate adding a limit applies immediately
+    manager_client.set_limit(&LimitKey::Withdraw, &100, &ONE_DAY_LEDGERS);
+    assert_eq!(
+        manager_client.remaining_limit(&LimitKey::Withdraw),
+        Some(100)
+    );
+
+    // set limit - validate tightening a limit applies immediately
+    manager_client.set_limit(&LimitKey::Withdraw, &50, &(ONE_DAY_LEDGERS * 2));
+    let limit = manager_client.limits().get(LimitKey::Withdraw).unwrap();
+    assert_eq!(limit.max_amount, 50);
+    assert_eq!(limit.period, ONE_DAY_LEDGERS * 2);
+    assert_eq!(manager_client.pending_changes().len(), 2);
+
+    // set limit - validate raising the max amount or shortening the period is queued
+    manager_client.set_limit(&LimitKey::Withdraw, &51, &(ONE_DAY_LEDGERS * 2));
+    manager_client.set_limit(&LimitKey::Withdraw, &50, &ONE_DAY_LEDGERS);
+    assert_eq!(
+        manager_client
+            .limits()
+            .get(LimitKey::Withdraw)
+            .unwrap()
+            .max_amount,
+        50
+    );
+    let pending_changes = manager_client.pending_changes();
+    assert_eq!(pending_changes.len(), 4);
+    assert_eq!(
+        pending_changes.get(2).unwrap().change,
+        ConfigChange::SetLimit(LimitKey::Withdraw, 51, ONE_DAY_LEDGERS * 2)
+    );
+
+    // remove limit - validate the limit must exist and the removal is queued
+    let result = manager_client.try_remove_limit(&LimitKey::Exit);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(111))));
+    manager_client.remove_limit(&LimitKey::Withdraw);
+    assert!(manager_client.limits().contains_key(LimitKey::Withdraw));
+    assert_eq!(
+        manager_client.pending_changes().get(4).unwrap().change,
+        ConfigChange::RemoveLimit(LimitKey::Withdraw)
+    );
+
+    // execute pending - the queued changes apply once the timelock passes
+    e.jump(timelock);
+    manager_client.execute_pending(&0);
+    assert_eq!(manager_client.guardian(), Some(pippin.clone()));
+    manager_client.execute_pending(&4);
+    assert_last_event(
+        &e,
+        &manager_client.address,
+        "remove_limit",
+        &frodo,
+        LimitKey::Withdraw.into_val(&e),
+    );
+    assert!(manager_client.limits().is_empty());
+    manager_client.execute_pending(&1);
+    assert_eq!(manager_client.guardian(), None);
+}
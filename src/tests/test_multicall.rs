@@ -0,0 +1,141 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    testutils::{Address as _, EnvTestConfig, MockAuth, MockAuthInvoke},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env, Error, IntoVal, Symbol, Vec,
+};
+
+use crate::permissions::SCOPE_HIGH;
+use crate::testutils::{
+    contract::Action, create_backstop_bootstrapper, create_backstop_manager_wasm,
+    create_blend_contracts, EnvTestUtils,
+};
+
+#[test]
+fn test_multicall() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.cost_estimate().budget().reset_unlimited();
+    e.set_default_info();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let usdc = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let blnd = e.register_stellar_asset_contract_v2(bombadil.clone());
+    let (contracts, pool) = create_blend_contracts(&e, &bombadil, &blnd.address(), &usdc.address());
+    let bootstrapper = create_backstop_bootstrapper(&e, &contracts);
+
+    // start manager (samwise) at scope 1
+    let (_, manager_client) = create_backstop_manager_wasm(
+        &e,
+        &frodo,
+        &samwise,
+        &1,
+        &bootstrapper.address,
+        &contracts.backstop_token.address,
+        &vec![&e, contracts.backstop.address.clone()],
+        &vec![&e, pool.clone()],
+        &0,
+    );
+    let blnd_token = TokenClient::new(&e, &blnd.address());
+    StellarAssetClient::new(&e, &blnd.address()).mint(&manager_client.address, &1001_0000000);
+    StellarAssetClient::new(&e, &usdc.address()).mint(&manager_client.address, &25_1000000);
+
+    // join the comet pool and deposit the minted backstop tokens
+    let lp_mint_amount = 100_0000000;
+    let max_amount_in: Vec<i128> = vec![&e, 1001_0000000, 25_1000000];
+    let actions = vec![
+        &e,
+        Action::CJoinPool(lp_mint_amount, max_amount_in.clone()),
+        Action::BDeposit(
+            contracts.backstop.address.clone(),
+            pool.clone(),
+            lp_mint_amount,
+        ),
+    ];
+
+    // multicall - validate every action requires its permission
+    e.set_auths(&[]);
+    let result = manager_client
+        .mock_auths(&[MockAuth {
+            address: &samwise,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: "multicall",
+                args: vec![&e, samwise.into_val(&e), actions.into_val(&e)],
+                sub_invokes: &[],
+            },
+        }])
+        .try_multicall(&samwise, &actions);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(4))));
+
+    // multicall - a failing action reverts the actions before it
+    e.mock_all_auths();
+    manager_client.set_manager_permissions(&samwise, &SCOPE_HIGH);
+    let failing_actions = vec![
+        &e,
+        Action::CJoinPool(lp_mint_amount, max_amount_in.clone()),
+        Action::BWithdraw(
+            contracts.backstop.address.clone(),
+            pool.clone(),
+            lp_mint_amount,
+        ),
+    ];
+    let result = manager_client.try_multicall(&samwise, &failing_actions);
+    assert!(result.is_err());
+    assert_eq!(contracts.backstop_token.balance(&manager_client.address), 0);
+    assert_eq!(manager_client.history(&0, &10).len(), 0);
+
+    // multicall - takes every action with a single authorization
+    e.set_auths(&[]);
+    manager_client
+        .mock_auths(&[MockAuth {
+            address: &samwise,
+            invoke: &MockAuthInvoke {
+                contract: &manager_client.address,
+                fn_name: "multicall",
+                args: vec![&e, samwise.into_val(&e), actions.into_val(&e)],
+                sub_invokes: &[],
+            },
+        }])
+        .multicall(&samwise, &actions);
+    assert_eq!(e.auths().len(), 1);
+    assert_eq!(e.auths()[0].0, samwise);
+    assert_eq!(contracts.backstop_token.balance(&manager_client.address), 0);
+    let backstop_bal = contracts
+        .backstop
+        .user_balance(&pool, &manager_client.address);
+    assert!(backstop_bal.shares > 0);
+    let history = manager_client.history(&0, &10);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(0).unwrap().action, Symbol::new(&e, "b_deposit"));
+    assert_eq!(
+        history.get(0).unwrap().amounts,
+        vec![&e, lp_mint_amount, backstop_bal.shares]
+    );
+    assert_eq!(
+        history.get(1).unwrap().action,
+        Symbol::new(&e, "c_join_pool")
+    );
+    assert_eq!(history.get(1).unwrap().caller, samwise);
+
+    // multicall - refunds tokens to the owner
+    e.mock_all_auths();
+    let blnd_balance = blnd_token.balance(&manager_client.address);
+    manager_client.multicall(
+        &samwise,
+        &vec![&e, Action::RefundToken(blnd.address(), blnd_balance)],
+    );
+    assert_eq!(blnd_token.balance(&manager_client.address), 0);
+    assert_eq!(blnd_token.balance(&frodo), blnd_balance);
+
+    // multicall - validates actions are not paused
+    manager_client.set_guardian(&Some(bombadil.clone()));
+    manager_client.pause();
+    let result = manager_client.try_multicall(&samwise, &actions);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(115))));
+}
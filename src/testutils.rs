@@ -1,14 +1,21 @@
 #![cfg(test)]
+#![allow(
+    clippy::duplicated_attributes,
+    clippy::inconsistent_digit_grouping,
+    clippy::needless_borrow,
+    clippy::too_many_arguments,
+    clippy::unit_arg
+)]
 
 use blend_contract_sdk::{pool::Client as PoolClient, testutils::BlendFixture};
 use soroban_sdk::{
-    testutils::{Address as _, BytesN as _, Ledger as _, LedgerInfo},
-    Address, BytesN, Env, String, Vec,
+    testutils::{Address as _, BytesN as _, Events, Ledger as _, LedgerInfo},
+    vec, Address, BytesN, Env, IntoVal, String, Symbol, Val, Vec,
 };
 
 use crate::dependencies::bootstrapper;
 
-mod contract {
+pub(crate) mod contract {
     soroban_sdk::contractimport!(
         file = "./target/wasm32-unknown-unknown/optimized/backstop_manager.wasm"
     );
@@ -33,6 +40,7 @@ pub fn create_backstop_manager_wasm<'a>(
     backstop_token: &Address,
     backstops: &Vec<Address>,
     pools: &Vec<Address>,
+    timelock: &u32,
 ) -> (Address, contract::Client<'a>) {
     let backstop_manager_address = e.register(
         contract::WASM,
@@ -44,6 +52,7 @@ pub fn create_backstop_manager_wasm<'a>(
             backstop_token,
             backstops.clone(),
             pools.clone(),
+            *timelock,
         ),
     );
     let backstop_manager_client: contract::Client<'a> =
@@ -55,6 +64,30 @@ pub fn create_backstop_manager_wasm<'a>(
 
 pub const ONE_DAY_LEDGERS: u32 = 17280;
 
+/// Assert the last event emitted during the last invocation was published by the contract
+/// with the action and caller as topics, and the expected data
+///
+/// ### Arguments
+/// * contract - The address of the contract that published the event
+/// * action - The action symbol of the event
+/// * caller - The caller of the action
+/// * data - The expected event data
+pub fn assert_last_event(e: &Env, contract: &Address, action: &str, caller: &Address, data: Val) {
+    let events = e.events().all();
+    assert!(!events.is_empty());
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            e,
+            (
+                contract.clone(),
+                (Symbol::new(e, action), caller.clone()).into_val(e),
+                data
+            )
+        ]
+    );
+}
+
 pub trait EnvTestUtils {
     /// Jump the env by the given amount of ledgers. Assumes 5 seconds per ledger.
     fn jump(&self, ledgers: u32);
@@ -0,0 +1,108 @@
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::{
+    errors::BackstopManagerErrors,
+    storage::{self, Pair},
+};
+
+/// Fixed point scalar for ratios with 7 decimals
+pub const SCALAR_7: i128 = 1_0000000;
+
+/// Validate `from` can take an action with a provided permission. If `from` is the owner,
+/// then the action is authorized. If `from` is a manager, then manager actions are validated
+/// to not be paused, and the manager is validated to not be expired and to have been granted
+/// every permission bit.
+///
+/// Auth for `from` must be required by the caller, so several actions can share a single
+/// authorization
+///
+/// ### Arguments
+/// * `from` - The address of the caller
+/// * `permission` - The permission bits required for the action
+///
+/// ### Errors
+/// * UnauthorizedError - The caller is not authorized to perform the action
+/// * ContractPaused - Manager actions are paused
+/// * ManagerExpired - The caller is a manager whose delegation has expired
+pub fn require_permission(e: &Env, from: &Address, permission: u32) {
+    if *from == storage::get_owner(e) {
+        return;
+    }
+    if storage::get_paused(e) {
+        panic_with_error!(e, BackstopManagerErrors::ContractPaused);
+    }
+    let manager_config = match storage::get_managers(e).get(from.clone()) {
+        Some(manager_config) => manager_config,
+        None => panic_with_error!(e, BackstopManagerErrors::UnauthorizedError),
+    };
+    if let Some(expires_at_ledger) = manager_config.expires_at_ledger {
+        if expires_at_ledger < e.ledger().sequence() {
+            panic_with_error!(e, BackstopManagerErrors::ManagerExpired);
+        }
+    }
+    if manager_config.permissions & permission != permission {
+        panic_with_error!(e, BackstopManagerErrors::UnauthorizedError);
+    }
+}
+
+/// Validate that the backstop and pool address are a valid pair
+///
+/// ### Arguments
+/// * `backstop` - The address of the backstop contract
+/// * `pool_address` - The address of the pool
+///
+/// ### Errors
+/// * InvalidContractAddress - The backstop and pool address are not a valid pair
+pub fn require_pair_valid(e: &Env, backstop: &Address, pool_address: &Address) {
+    let pair = Pair {
+        backstop: backstop.clone(),
+        pool: pool_address.clone(),
+    };
+    if !storage::is_pair(e, &pair)
+        || !storage::is_valid_backstop(e, backstop)
+        || !storage::is_valid_pool(e, pool_address)
+    {
+        panic_with_error!(e, BackstopManagerErrors::InvalidContractAddress);
+    }
+}
+
+/// Validate that the backstop is included in the valid list
+///
+/// ### Arguments
+/// * `backstop` - The address of the backstop contract
+///
+/// ### Errors
+/// * InvalidContractAddress - The backstop is not included in the valid list
+pub fn require_backstop_valid(e: &Env, backstop: &Address) {
+    if !storage::is_valid_backstop(e, backstop) {
+        panic_with_error!(e, BackstopManagerErrors::InvalidContractAddress);
+    }
+}
+
+/// Validate that the pool is included in the valid list
+///
+/// ### Arguments
+/// * `pool_address` - The address of the pool
+///
+/// ### Errors
+/// * InvalidContractAddress - The pool is not included in the valid list
+pub fn require_pool_valid(e: &Env, pool_address: &Address) {
+    if !storage::is_valid_pool(e, pool_address) {
+        panic_with_error!(e, BackstopManagerErrors::InvalidContractAddress);
+    }
+}
+
+/// Validate that at least `min_ratio` of the amount in was received out
+///
+/// ### Arguments
+/// * `amount_in` - The amount given
+/// * `amount_out` - The amount received
+/// * `min_ratio` - The minimum amount out per amount in, with 7 decimals
+///
+/// ### Errors
+/// * MinTokensOutNotMet - Less than the minimum ratio was received
+pub fn require_min_out_ratio(e: &Env, amount_in: i128, amount_out: i128, min_ratio: i128) {
+    if amount_out * SCALAR_7 < amount_in * min_ratio {
+        panic_with_error!(e, BackstopManagerErrors::MinTokensOutNotMet);
+    }
+}